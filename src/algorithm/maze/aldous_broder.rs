@@ -0,0 +1,81 @@
+use crate::{algorithm::{maze::{between, fill, rooms_around}, set_node, Algorithm, AlgorithmResult, AlgorithmType, Coord}, grid2d::Grid2D, node::NodeType};
+use rand::prelude::*;
+
+// a random walk over the whole grid that carves its way into every room it hasn't been to yet.
+// every maze is as likely as any other, but the last few rooms can take a long time to stumble on
+pub struct AldousBroder {
+    current: Option<Coord>, // where the walk is, drawn as one until it's done
+    remaining: usize, // rooms that haven't been reached yet
+    started: bool,
+    rng: StdRng,
+}
+
+impl AldousBroder {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            current: None,
+            remaining: 0,
+            started: false,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Algorithm for AldousBroder {
+    fn step(&mut self, grid: &mut Grid2D) -> AlgorithmResult {
+        let mut changes = Vec::new();
+
+        if !self.started {
+            let height = grid.height() as i32;
+            let width = grid.width() as i32;
+
+            fill(grid, &mut changes, NodeType::Wall);
+
+            if !grid.is_empty() {
+                let start = (
+                    self.rng.random_range(0..(width + 1) / 2) * 2,
+                    self.rng.random_range(0..(height + 1) / 2) * 2,
+                );
+
+                set_node(grid, &mut changes, start, NodeType::Walk);
+                self.current = Some(start);
+                self.remaining = (((width + 1) / 2) * ((height + 1) / 2)) as usize - 1;
+            }
+            self.started = true;
+
+            return AlgorithmResult::ModifiedGrid(changes);
+        }
+
+        let Some(current) = self.current else {
+            return AlgorithmResult::Done(None);
+        };
+
+        // every room has been reached, the walk doesn't need to be shown anymore
+        if self.remaining == 0 {
+            set_node(grid, &mut changes, current, NodeType::Empty);
+            self.current = None;
+
+            return AlgorithmResult::ModifiedGrid(changes);
+        }
+
+        let Some(&next) = rooms_around(grid, current).choose(&mut self.rng) else {
+            return AlgorithmResult::Done(None);
+        };
+
+        // walls only come down on the way into a room for the first time
+        if grid[next].node_type == NodeType::Wall {
+            set_node(grid, &mut changes, between(current, next), NodeType::Empty);
+            self.remaining -= 1;
+        }
+
+        set_node(grid, &mut changes, current, NodeType::Empty);
+        set_node(grid, &mut changes, next, NodeType::Walk);
+        self.current = Some(next);
+
+        AlgorithmResult::ModifiedGrid(changes)
+    }
+
+    fn algorithm_type(&self) -> AlgorithmType {
+        AlgorithmType::MazeGeneration
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{algorithm::{maze::{fill, last_room}, set_node, Algorithm, AlgorithmResult, AlgorithmType, Coord}, grid2d::Grid2D, node::NodeType};
+use rand::prelude::*;
+
+// room by room, row by row, every room opens up either to the north or to the west. the top row and
+// the left column end up as long corridors and every path leads diagonally up to the top left corner
+pub struct BinaryTree {
+    cursor: Coord, // room that's carved next
+    right: i32, // last room of a row
+    bottom: i32, // last row of rooms
+    started: bool,
+    rng: StdRng,
+}
+
+impl BinaryTree {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            cursor: (0, 0),
+            right: 0,
+            bottom: -1,
+            started: false,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Algorithm for BinaryTree {
+    fn step(&mut self, grid: &mut Grid2D) -> AlgorithmResult {
+        let mut changes = Vec::new();
+
+        if !self.started {
+            fill(grid, &mut changes, NodeType::Wall);
+
+            if let Some((right, bottom)) = last_room(grid) {
+                self.right = right;
+                self.bottom = bottom;
+            }
+            self.started = true;
+
+            return AlgorithmResult::ModifiedGrid(changes);
+        }
+
+        let (x, y) = self.cursor;
+        if y > self.bottom {
+            return AlgorithmResult::Done(None);
+        }
+
+        set_node(grid, &mut changes, (x, y), NodeType::Empty);
+
+        let mut openings = Vec::new();
+        if y > 0 {
+            openings.push((x, y - 1));
+        }
+        if x > 0 {
+            openings.push((x - 1, y));
+        }
+
+        // the top left room is the only one without either
+        if let Some(&opening) = openings.choose(&mut self.rng) {
+            set_node(grid, &mut changes, opening, NodeType::Empty);
+        }
+
+        self.cursor = if x < self.right { (x + 2, y) } else { (0, y + 2) };
+
+        AlgorithmResult::ModifiedGrid(changes)
+    }
+
+    fn highlighted_row(&self) -> Option<i32> {
+        (self.started && self.cursor.1 <= self.bottom).then_some(self.cursor.1)
+    }
+
+    fn algorithm_type(&self) -> AlgorithmType {
+        AlgorithmType::MazeGeneration
+    }
+}
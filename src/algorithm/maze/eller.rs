@@ -0,0 +1,158 @@
+use std::collections::HashSet;
+
+use crate::{algorithm::{maze::{fill, last_room}, set_label, set_node, Algorithm, AlgorithmResult, AlgorithmType, CellChange, Coord}, grid2d::Grid2D, node::NodeType};
+use rand::prelude::*;
+
+// row by row while only ever keeping one row in memory. rooms in a row belong to sets: neighbors in
+// different sets get joined at random, then every set opens up to the row below at least once.
+// the last row joins whatever is still apart. the row's sets are drawn as labels
+pub struct Eller {
+    sets: Vec<Option<usize>>, // set of every room in the current row, by column
+    below: Vec<Option<usize>>, // sets handed down to the rooms of the next row
+    went_down: HashSet<usize>, // sets of the current row that opened up to the next one
+    next_set: usize,
+    cursor: Coord, // room that's worked on next
+    joining: bool, // first pass over a row, the second one goes down
+    right: i32, // last room of a row
+    bottom: i32, // last row of rooms
+    started: bool,
+    rng: StdRng,
+}
+
+impl Eller {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            sets: Vec::new(),
+            below: Vec::new(),
+            went_down: HashSet::new(),
+            next_set: 0,
+            cursor: (0, 0),
+            joining: true,
+            right: 0,
+            bottom: -1,
+            started: false,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    // labels of a row are only there while it's being worked on
+    fn clear_labels(grid: &mut Grid2D, changes: &mut Vec<CellChange>, y: i32) {
+        for x in 0..grid.width() as i32 {
+            if grid[(x, y)].label.is_some() {
+                set_label(grid, changes, (x, y), None);
+            }
+        }
+    }
+
+    fn join(&mut self, grid: &mut Grid2D, changes: &mut Vec<CellChange>, (x, y): Coord) {
+        let column = (x / 2) as usize;
+        let set = *self.sets[column].get_or_insert_with(|| {
+            self.next_set += 1;
+            self.next_set - 1
+        });
+
+        set_node(grid, changes, (x, y), NodeType::Empty);
+        set_label(grid, changes, (x, y), Some(set as u32));
+
+        let Some(left) = column.checked_sub(1).and_then(|left| self.sets[left]) else {
+            return;
+        };
+
+        // the last row can't leave anything for later, so everything that's still apart is joined
+        let joined = left != set && (y == self.bottom || self.rng.random_bool(0.5));
+        if !joined {
+            return;
+        }
+
+        set_node(grid, changes, (x - 1, y), NodeType::Empty);
+        set_label(grid, changes, (x - 1, y), Some(left as u32));
+
+        // the whole set moves over, including rooms further along that came down from the row above
+        for other in self.sets.iter_mut().filter(|other| **other == Some(set)) {
+            *other = Some(left);
+        }
+        for x in 0..grid.width() as i32 {
+            if grid[(x, y)].label == Some(set as u32) {
+                set_label(grid, changes, (x, y), Some(left as u32));
+            }
+        }
+    }
+
+    fn go_down(&mut self, grid: &mut Grid2D, changes: &mut Vec<CellChange>, (x, y): Coord) {
+        let column = (x / 2) as usize;
+        let Some(set) = self.sets[column] else {
+            return;
+        };
+
+        // a set that's about to run out of rooms in this row has to go down here if it hasn't yet
+        let last_of_set = self.sets[column + 1..].iter().all(|&other| other != Some(set));
+        let must = last_of_set && !self.went_down.contains(&set);
+
+        if must || self.rng.random_bool(0.5) {
+            set_node(grid, changes, (x, y + 1), NodeType::Empty);
+            set_node(grid, changes, (x, y + 2), NodeType::Empty);
+            set_label(grid, changes, (x, y + 2), Some(set as u32));
+
+            self.below[column] = Some(set);
+            self.went_down.insert(set);
+        }
+    }
+}
+
+impl Algorithm for Eller {
+    fn step(&mut self, grid: &mut Grid2D) -> AlgorithmResult {
+        let mut changes = Vec::new();
+
+        if !self.started {
+            fill(grid, &mut changes, NodeType::Wall);
+
+            if let Some((right, bottom)) = last_room(grid) {
+                self.right = right;
+                self.bottom = bottom;
+            }
+
+            let columns = (self.right / 2 + 1) as usize;
+            self.sets = vec![None; columns];
+            self.below = vec![None; columns];
+            self.started = true;
+
+            return AlgorithmResult::ModifiedGrid(changes);
+        }
+
+        let (x, y) = self.cursor;
+        if y > self.bottom {
+            return AlgorithmResult::Done(None);
+        }
+
+        if self.joining {
+            self.join(grid, &mut changes, (x, y));
+        } else {
+            self.go_down(grid, &mut changes, (x, y));
+        }
+
+        if x < self.right {
+            self.cursor = (x + 2, y);
+        } else if self.joining && y < self.bottom {
+            self.cursor = (0, y);
+            self.joining = false;
+        } else {
+            // on to the next row, with the sets that came down
+            Eller::clear_labels(grid, &mut changes, y);
+
+            self.sets = std::mem::replace(&mut self.below, vec![None; self.sets.len()]);
+            self.went_down.clear();
+            self.cursor = (0, y + 2);
+            self.joining = true;
+        }
+
+        AlgorithmResult::ModifiedGrid(changes)
+    }
+
+    fn highlighted_row(&self) -> Option<i32> {
+        (self.started && self.cursor.1 <= self.bottom).then_some(self.cursor.1)
+    }
+
+    fn algorithm_type(&self) -> AlgorithmType {
+        AlgorithmType::MazeGeneration
+    }
+}
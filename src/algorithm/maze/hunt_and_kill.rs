@@ -0,0 +1,98 @@
+use crate::{algorithm::{maze::{between, fill, room_neighbors}, set_node, Algorithm, AlgorithmResult, AlgorithmType, Coord}, grid2d::Grid2D, node::NodeType};
+use rand::prelude::*;
+
+// a random walk into rooms it hasn't been to, and once it's stuck a scan from the top,
+// a row of rooms per step, for an unvisited room next to the maze to carry on from
+pub struct HuntAndKill {
+    current: Option<Coord>, // None while hunting
+    scan_row: i32,
+    started: bool,
+    rng: StdRng,
+}
+
+impl HuntAndKill {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            current: None,
+            scan_row: 0,
+            started: false,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Algorithm for HuntAndKill {
+    fn step(&mut self, grid: &mut Grid2D) -> AlgorithmResult {
+        let mut changes = Vec::new();
+
+        if !self.started {
+            let height = grid.height() as i32;
+            let width = grid.width() as i32;
+
+            fill(grid, &mut changes, NodeType::Wall);
+
+            if !grid.is_empty() {
+                let start = (
+                    self.rng.random_range(0..(width + 1) / 2) * 2,
+                    self.rng.random_range(0..(height + 1) / 2) * 2,
+                );
+
+                set_node(grid, &mut changes, start, NodeType::Empty);
+                self.current = Some(start);
+            }
+            self.started = true;
+
+            return AlgorithmResult::ModifiedGrid(changes);
+        }
+
+        // kill: walk on into a random unvisited room
+        if let Some(current) = self.current {
+            match room_neighbors(grid, current, NodeType::Wall).choose(&mut self.rng) {
+                Some(&next) => {
+                    set_node(grid, &mut changes, between(current, next), NodeType::Empty);
+                    set_node(grid, &mut changes, next, NodeType::Empty);
+                    self.current = Some(next);
+                },
+                None => {
+                    self.current = None;
+                    self.scan_row = 0;
+                },
+            }
+
+            return AlgorithmResult::ModifiedGrid(changes);
+        }
+
+        // hunt: the rooms of one row, looking for one that's unvisited but next to the maze
+        if self.scan_row >= grid.height() as i32 {
+            return AlgorithmResult::Done(None);
+        }
+
+        for x in (0..grid.width() as i32).step_by(2) {
+            let room = (x, self.scan_row);
+            if grid[room].node_type != NodeType::Wall {
+                continue;
+            }
+
+            if let Some(&visited) = room_neighbors(grid, room, NodeType::Empty).choose(&mut self.rng) {
+                set_node(grid, &mut changes, between(room, visited), NodeType::Empty);
+                set_node(grid, &mut changes, room, NodeType::Empty);
+                self.current = Some(room);
+
+                return AlgorithmResult::ModifiedGrid(changes);
+            }
+        }
+
+        self.scan_row += 2;
+
+        AlgorithmResult::ModifiedGrid(changes)
+    }
+
+    // the row that's being scanned
+    fn highlighted_row(&self) -> Option<i32> {
+        (self.started && self.current.is_none()).then_some(self.scan_row)
+    }
+
+    fn algorithm_type(&self) -> AlgorithmType {
+        AlgorithmType::MazeGeneration
+    }
+}
@@ -0,0 +1,145 @@
+use crate::{algorithm::{maze::fill, set_label, set_node, Algorithm, AlgorithmResult, AlgorithmType, Coord}, grid2d::Grid2D, node::NodeType};
+use rand::prelude::*;
+
+// union-find over every node of the grid, although only the rooms ever get joined
+struct DisjointSet {
+    parents: Vec<usize>,
+    members: Vec<Vec<Coord>>, // nodes of each set, kept on its root so a merge can relabel them
+}
+
+impl DisjointSet {
+    fn new(size: usize) -> Self {
+        Self {
+            parents: (0..size).collect(),
+            members: vec![Vec::new(); size],
+        }
+    }
+
+    // root of the set, halving the path on the way up
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parents[i] != i {
+            self.parents[i] = self.parents[self.parents[i]];
+            i = self.parents[i];
+        }
+
+        i
+    }
+
+    // hangs the smaller set under the bigger one, returns the root that's left and the nodes that moved
+    fn union(&mut self, a: usize, b: usize) -> (usize, Vec<Coord>) {
+        let (root, child) = if self.members[a].len() >= self.members[b].len() { (a, b) } else { (b, a) };
+
+        self.parents[child] = root;
+        let moved = std::mem::take(&mut self.members[child]);
+        self.members[root].extend_from_slice(&moved);
+
+        (root, moved)
+    }
+}
+
+// rooms on even coordinates each start out as their own set, walls between two sets are knocked down
+pub struct Kruskal {
+    walls: Vec<Coord>,
+    sets: DisjointSet,
+    started: bool,
+    labelled: bool, // the sets are still drawn
+    rng: StdRng,
+}
+
+impl Kruskal {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            walls: Vec::new(),
+            sets: DisjointSet::new(0),
+            started: false,
+            labelled: false,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    // the two rooms a wall separates, it's between them either horizontally or vertically
+    fn rooms(wall: Coord) -> (Coord, Coord) {
+        if wall.0 % 2 == 1 {
+            ((wall.0 - 1, wall.1), (wall.0 + 1, wall.1))
+        } else {
+            ((wall.0, wall.1 - 1), (wall.0, wall.1 + 1))
+        }
+    }
+}
+
+impl Algorithm for Kruskal {
+    fn step(&mut self, grid: &mut Grid2D) -> AlgorithmResult {
+        let mut changes = Vec::new();
+        let width = grid.width() as i32;
+        let index = |coord: Coord| (coord.1 * width + coord.0) as usize;
+
+        if !self.started {
+            self.sets = DisjointSet::new(grid.width() * grid.height());
+            fill(grid, &mut changes, NodeType::Wall);
+
+            for y in 0..grid.height() as i32 {
+                for x in 0..width {
+                    if x % 2 == 0 && y % 2 == 0 {
+                        set_node(grid, &mut changes, (x, y), NodeType::Empty);
+                        set_label(grid, &mut changes, (x, y), Some(index((x, y)) as u32));
+                        self.sets.members[index((x, y))].push((x, y));
+                    } else {
+                        // only walls with a room on both sides can join anything
+                        let (a, b) = Kruskal::rooms((x, y));
+                        if x % 2 != y % 2 && grid.in_bounds(a) && grid.in_bounds(b) {
+                            self.walls.push((x, y));
+                        }
+                    }
+                }
+            }
+
+            self.walls.shuffle(&mut self.rng);
+            self.started = true;
+            self.labelled = true;
+
+            return AlgorithmResult::ModifiedGrid(changes);
+        }
+
+        let Some(wall) = self.walls.pop() else {
+            if !self.labelled {
+                return AlgorithmResult::Done(None);
+            }
+
+            // everything's one set by now, the colors have said all they can
+            let labelled: Vec<Coord> = grid.enumerate().filter(|(_, node)| node.label.is_some()).map(|(coord, _)| coord).collect();
+            for coord in labelled {
+                set_label(grid, &mut changes, coord, None);
+            }
+            self.labelled = false;
+
+            return AlgorithmResult::ModifiedGrid(changes);
+        };
+
+        let (a, b) = Kruskal::rooms(wall);
+        let (root_a, root_b) = (self.sets.find(index(a)), self.sets.find(index(b)));
+
+        // rooms that are already connected keep the wall, otherwise there'd be a loop.
+        // the step still counts, it's just one where nothing changes
+        if root_a == root_b {
+            return AlgorithmResult::ModifiedGrid(changes);
+        }
+
+        let (root, moved) = self.sets.union(root_a, root_b);
+        let label = Some(root as u32);
+
+        set_node(grid, &mut changes, wall, NodeType::Empty);
+        set_label(grid, &mut changes, wall, label);
+        self.sets.members[root].push(wall);
+
+        for coord in moved {
+            set_label(grid, &mut changes, coord, label);
+        }
+
+        AlgorithmResult::ModifiedGrid(changes)
+    }
+
+    fn algorithm_type(&self) -> AlgorithmType {
+        AlgorithmType::MazeGeneration
+    }
+}
+
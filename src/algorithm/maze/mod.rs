@@ -1 +1,143 @@
-pub mod noise_map;
\ No newline at end of file
+use crate::{algorithm::{maze::{aldous_broder::AldousBroder, binary_tree::BinaryTree, eller::Eller, hunt_and_kill::HuntAndKill, kruskal::Kruskal, noise_map::NoiseMap, prims::Prims, recursive_backtracker::RecursiveBacktracker, recursive_division::RecursiveDivision, sidewinder::Sidewinder, wilson::Wilson}, set_node, Algorithm, CellChange, Coord}, grid2d::Grid2D, node::NodeType};
+
+pub mod aldous_broder;
+pub mod binary_tree;
+pub mod eller;
+pub mod hunt_and_kill;
+pub mod kruskal;
+pub mod noise_map;
+pub mod prims;
+pub mod recursive_backtracker;
+pub mod recursive_division;
+pub mod sidewinder;
+pub mod wilson;
+
+// the carving generators share a layout: rooms on even coordinates, walls in between.
+// these lead from a room to the ones next to it
+pub const ROOM_DIRECTIONS: [Coord; 4] = [(0, -2), (2, 0), (0, 2), (-2, 0)];
+
+// every generator as it's typed on the command line, noise once without and once with mud
+pub const NAMES: [&str; 12] = [
+    "backtracker", "prims", "kruskal", "wilson", "aldous-broder", "hunt-and-kill", "division",
+    "eller", "sidewinder", "binary-tree", "noise:10", "noise:10:25",
+];
+
+// "backtracker", "prims", "noise:10" or any other of the NAMES -> the generator, the same seed gives the same maze
+pub fn from_name(name: &str, seed: u64) -> Option<Box<dyn Algorithm>> {
+    let mut parts = name.split(':');
+
+    let algorithm: Box<dyn Algorithm> = match parts.next()? {
+        "backtracker" => Box::new(RecursiveBacktracker::new(seed)),
+        "prims" => Box::new(Prims::new(seed)),
+        "kruskal" => Box::new(Kruskal::new(seed)),
+        "wilson" => Box::new(Wilson::new(seed)),
+        "aldous-broder" => Box::new(AldousBroder::new(seed)),
+        "hunt-and-kill" => Box::new(HuntAndKill::new(seed)),
+        "division" => Box::new(RecursiveDivision::new(seed)),
+        "eller" => Box::new(Eller::new(seed)),
+        "sidewinder" => Box::new(Sidewinder::new(seed)),
+        "binary-tree" => Box::new(BinaryTree::new(seed)),
+        "noise" => {
+            let fill = parts.next()?.parse().ok()?;
+            let mud = parts.next().map_or(Some(0), |mud| mud.parse().ok())?;
+
+            Box::new(NoiseMap::new(fill, mud, seed))
+        },
+        _ => return None,
+    };
+
+    // nothing left over, "prims:10" is a typo rather than prims
+    parts.next().is_none().then_some(algorithm)
+}
+
+
+// the wall that separates two neighboring rooms
+pub fn between(from: Coord, to: Coord) -> Coord {
+    ((from.0 + to.0) / 2, (from.1 + to.1) / 2)
+}
+
+// rooms next to the given one, whatever they are
+pub fn rooms_around(grid: &Grid2D, room: Coord) -> Vec<Coord> {
+    ROOM_DIRECTIONS.iter()
+        .map(|(dx, dy)| (room.0 + dx, room.1 + dy))
+        .filter(|&neighbor| grid.in_bounds(neighbor))
+        .collect()
+}
+
+// rooms next to the given one that are of a certain type, e.g. walls that haven't been carved yet
+pub fn room_neighbors(grid: &Grid2D, room: Coord, node_type: NodeType) -> Vec<Coord> {
+    rooms_around(grid, room).into_iter()
+        .filter(|&neighbor| grid[neighbor].node_type == node_type)
+        .collect()
+}
+
+// the room furthest right and down, none on an empty grid. with an even width or height the last column or row
+// has no rooms in it, only walls
+pub fn last_room(grid: &Grid2D) -> Option<Coord> {
+    (!grid.is_empty()).then(|| ((grid.width() as i32 - 1) / 2 * 2, (grid.height() as i32 - 1) / 2 * 2))
+}
+
+// turns every node of the grid into the same type, how the generators start out
+pub fn fill(grid: &mut Grid2D, changes: &mut Vec<CellChange>, node_type: NodeType) {
+    for y in 0..grid.height() as i32 {
+        for x in 0..grid.width() as i32 {
+            set_node(grid, changes, (x, y), node_type);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{grid2d::Connectivity, node::Node, runner::run_to_completion};
+
+    use super::*;
+
+    // open nodes reachable from the top left room without going through walls
+    fn reachable(grid: &Grid2D) -> usize {
+        let mut seen = vec![(0, 0)];
+        let mut stack = vec![(0, 0)];
+
+        while let Some(coord) = stack.pop() {
+            for neighbor in grid.neighbors(coord, Connectivity::Four) {
+                if grid[neighbor].node_type == NodeType::Empty && !seen.contains(&neighbor) {
+                    seen.push(neighbor);
+                    stack.push(neighbor);
+                }
+            }
+        }
+
+        seen.len()
+    }
+
+    // every room open, and just enough walls knocked down to connect them: a spanning tree without loops
+    #[test]
+    fn carving_generators_make_perfect_mazes() {
+        for name in NAMES.iter().filter(|name| !name.starts_with("noise")) {
+            for (width, height) in [(21, 11), (20, 10), (1, 1), (2, 2), (1, 7), (9, 1)] {
+                for seed in 0..3 {
+                    // starting out on mud also checks that carving leaves no weights behind
+                    let mut grid = Grid2D::filled(width, height, Node::new(NodeType::Mud));
+                    let mut generator = from_name(name, seed).unwrap();
+                    run_to_completion(generator.as_mut(), &mut grid);
+
+                    let maze = format!("{} on {} x {} with seed {}", name, width, height, seed);
+                    let rooms = width.div_ceil(2) * height.div_ceil(2);
+                    let open = grid.iter().filter(|node| node.node_type == NodeType::Empty).count();
+
+                    assert!(grid.iter().all(|node| matches!(node.node_type, NodeType::Empty | NodeType::Wall)), "{}", maze);
+                    assert!(grid.iter().all(|node| node.weight == 1 && node.label.is_none()), "{}", maze);
+                    assert_eq!(open, 2 * rooms - 1, "{}", maze);
+                    assert_eq!(reachable(&grid), open, "{}", maze);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn last_room_skips_a_last_row_or_column_of_walls() {
+        assert_eq!(last_room(&Grid2D::new(21, 11)), Some((20, 10)));
+        assert_eq!(last_room(&Grid2D::new(20, 10)), Some((18, 8)));
+        assert_eq!(last_room(&Grid2D::new(1, 1)), Some((0, 0)));
+        assert_eq!(last_room(&Grid2D::new(0, 0)), None);
+    }
+}
@@ -1,52 +1,53 @@
-use crate::{algorithm::{Algorithm, AlgorithmResult, AlgorithmType, Coord}, grid::{Node, NodeType}};
+use crate::{algorithm::{Algorithm, AlgorithmResult, AlgorithmType, CellChange, Coord}, grid2d::Grid2D, node::{Node, NodeType}};
 use rand::prelude::*;
 
 pub struct NoiseMap {
     next: Coord,
     fill_percentage: i32,
-    rng: ThreadRng,
+    mud_percentage: i32,
+    rng: StdRng,
 }
 
 impl NoiseMap {
-    pub fn new(f: i32) -> Self {
+    pub fn new(f: i32, m: i32, seed: u64) -> Self {
         Self {
             next: (0, 0),
             fill_percentage: f,
-            rng: rand::rng()
+            mud_percentage: m,
+            rng: StdRng::seed_from_u64(seed),
         }
     }
 }
 
 impl Algorithm for NoiseMap {
-    fn step(&mut self, grid: &mut Vec<Vec<Node>>) -> AlgorithmResult {
-        let height = grid.len() as i32;
-        let width = grid[0].len() as i32;
+    fn step(&mut self, grid: &mut Grid2D) -> AlgorithmResult {
+        let width = grid.width() as i32;
+
+        // went past the last row
+        let Some(node) = grid.get_mut(self.next) else {
+            return AlgorithmResult::Done(None);
+        };
+
+        // walls take the first slice of the roll, mud the next one
+        let roll = self.rng.random_range(0..=100);
+        let node_type = if roll <= self.fill_percentage {
+            NodeType::Wall
+        } else if roll <= self.fill_percentage + self.mud_percentage {
+            NodeType::Mud
+        } else {
+            NodeType::Empty
+        };
 
-        let node = &mut grid[self.next.1 as usize][self.next.0 as usize];
+        *node = Node::new(node_type);
+        let change = CellChange { coord: self.next, node_type, weight: node.weight, label: None };
 
-        if self.rng.random_range(0..=100) <= self.fill_percentage {
-            node.node_type = NodeType::Wall;
+        if self.next.0 == width-1 {
+            self.next = (0, self.next.1 + 1);
         } else {
-            node.node_type = NodeType::Empty;
+            self.next = (self.next.0 + 1, self.next.1);
         }
 
-        if self.next.0 == width-1 && self.next.1 == height-1 {
-            AlgorithmResult::Done(None)
-        } else {
-            let x: i32;
-            let y: i32;
-
-            if self.next.0 == width-1 {
-                x = 0;
-                y = self.next.1 + 1;
-            } else {
-                x = self.next.0 + 1;
-                y = self.next.1;
-            }
-
-            self.next = (x, y);
-            AlgorithmResult::ModifiedGrid
-        }
+        AlgorithmResult::ModifiedGrid(vec![change])
     }
 
     fn algorithm_type(&self) -> AlgorithmType {
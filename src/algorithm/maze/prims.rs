@@ -0,0 +1,69 @@
+use crate::{algorithm::{maze::{between, fill, room_neighbors}, set_node, Algorithm, AlgorithmResult, AlgorithmType, CellChange, Coord}, grid2d::Grid2D, node::NodeType};
+use rand::prelude::*;
+
+pub struct Prims {
+    frontier: Vec<Coord>,
+    started: bool,
+    rng: StdRng,
+}
+
+impl Prims {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            frontier: Vec::new(),
+            started: false,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    fn carve(&mut self, grid: &mut Grid2D, changes: &mut Vec<CellChange>, cell: Coord) {
+        set_node(grid, changes, cell, NodeType::Empty);
+
+        for neighbor in room_neighbors(grid, cell, NodeType::Wall) {
+            set_node(grid, changes, neighbor, NodeType::Frontier);
+            self.frontier.push(neighbor);
+        }
+    }
+}
+
+impl Algorithm for Prims {
+    fn step(&mut self, grid: &mut Grid2D) -> AlgorithmResult {
+        let mut changes = Vec::new();
+
+        if !self.started {
+            let height = grid.height() as i32;
+            let width = grid.width() as i32;
+
+            fill(grid, &mut changes, NodeType::Wall);
+
+            let start = (
+                self.rng.random_range(0..(width + 1) / 2) * 2,
+                self.rng.random_range(0..(height + 1) / 2) * 2,
+            );
+
+            self.carve(grid, &mut changes, start);
+            self.started = true;
+
+            return AlgorithmResult::ModifiedGrid(changes);
+        }
+
+        if self.frontier.is_empty() {
+            return AlgorithmResult::Done(None);
+        }
+
+        let cell = self.frontier.swap_remove(self.rng.random_range(0..self.frontier.len()));
+
+        // every frontier cell borders at least one carved room
+        if let Some(&parent) = room_neighbors(grid, cell, NodeType::Empty).choose(&mut self.rng) {
+            set_node(grid, &mut changes, between(cell, parent), NodeType::Empty);
+        }
+
+        self.carve(grid, &mut changes, cell);
+
+        AlgorithmResult::ModifiedGrid(changes)
+    }
+
+    fn algorithm_type(&self) -> AlgorithmType {
+        AlgorithmType::MazeGeneration
+    }
+}
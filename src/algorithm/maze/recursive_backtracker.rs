@@ -0,0 +1,63 @@
+use crate::{algorithm::{maze::{between, fill, room_neighbors}, set_node, Algorithm, AlgorithmResult, AlgorithmType, Coord}, grid2d::Grid2D, node::NodeType};
+use rand::prelude::*;
+
+pub struct RecursiveBacktracker {
+    stack: Vec<Coord>,
+    started: bool,
+    rng: StdRng,
+}
+
+impl RecursiveBacktracker {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            stack: Vec::new(),
+            started: false,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Algorithm for RecursiveBacktracker {
+    fn step(&mut self, grid: &mut Grid2D) -> AlgorithmResult {
+        let mut changes = Vec::new();
+
+        // first step fills the grid so there's something to carve
+        if !self.started {
+            fill(grid, &mut changes, NodeType::Wall);
+
+            set_node(grid, &mut changes, (0, 0), NodeType::Visited);
+            self.stack.push((0, 0));
+            self.started = true;
+
+            return AlgorithmResult::ModifiedGrid(changes);
+        }
+
+        let Some(&current) = self.stack.last() else {
+            return AlgorithmResult::Done(None);
+        };
+
+        // cells on the stack are drawn as visited, so the current DFS path stays visible
+        if let Some(&next) = room_neighbors(grid, current, NodeType::Wall).choose(&mut self.rng) {
+            let wall = between(current, next);
+
+            set_node(grid, &mut changes, wall, NodeType::Visited);
+            set_node(grid, &mut changes, next, NodeType::Visited);
+            self.stack.push(next);
+        } else {
+            // dead end, back up to the previous cell
+            self.stack.pop();
+            set_node(grid, &mut changes, current, NodeType::Empty);
+
+            if let Some(&parent) = self.stack.last() {
+                let wall = between(current, parent);
+                set_node(grid, &mut changes, wall, NodeType::Empty);
+            }
+        }
+
+        AlgorithmResult::ModifiedGrid(changes)
+    }
+
+    fn algorithm_type(&self) -> AlgorithmType {
+        AlgorithmType::MazeGeneration
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{algorithm::{maze::last_room, set_node, Algorithm, AlgorithmResult, AlgorithmType}, grid2d::Grid2D, node::NodeType};
+use rand::prelude::*;
+
+// part of the grid that's still open, bounds are rooms (even coordinates) and inclusive
+#[derive(Clone, Copy)]
+struct Chamber {
+    left: i32,
+    top: i32,
+    right: i32,
+    bottom: i32,
+}
+
+impl Chamber {
+    // rooms across and down
+    fn rooms(&self) -> (i32, i32) {
+        ((self.right - self.left) / 2 + 1, (self.bottom - self.top) / 2 + 1)
+    }
+}
+
+// the other way around from the carving generators: it starts out empty and splits it up with walls.
+// every step puts a wall with a single gap across a chamber, the two halves get split up later on
+pub struct RecursiveDivision {
+    chambers: Vec<Chamber>, // still to be divided, the last one first, the same order the recursion would take
+    started: bool,
+    rng: StdRng,
+}
+
+impl RecursiveDivision {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            chambers: Vec::new(),
+            started: false,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    // a random even number between the two (inclusive) even ones
+    fn random_room(&mut self, from: i32, to: i32) -> i32 {
+        from + self.rng.random_range(0..=(to - from) / 2) * 2
+    }
+
+    // a random odd number between the two even ones
+    fn random_wall(&mut self, from: i32, to: i32) -> i32 {
+        from + 1 + self.rng.random_range(0..(to - from) / 2) * 2
+    }
+
+    // halves with more than a single room still need dividing
+    fn push(&mut self, chamber: Chamber) {
+        if chamber.rooms() != (1, 1) {
+            self.chambers.push(chamber);
+        }
+    }
+}
+
+impl Algorithm for RecursiveDivision {
+    fn step(&mut self, grid: &mut Grid2D) -> AlgorithmResult {
+        let mut changes = Vec::new();
+
+        // first step clears the grid, apart from the last row or column when there's no room for rooms in it
+        if !self.started {
+            if let Some((right, bottom)) = last_room(grid) {
+                for y in 0..grid.height() as i32 {
+                    for x in 0..grid.width() as i32 {
+                        let node_type = if x > right || y > bottom { NodeType::Wall } else { NodeType::Empty };
+                        set_node(grid, &mut changes, (x, y), node_type);
+                    }
+                }
+
+                self.push(Chamber { left: 0, top: 0, right, bottom });
+            }
+            self.started = true;
+
+            return AlgorithmResult::ModifiedGrid(changes);
+        }
+
+        let Some(chamber) = self.chambers.pop() else {
+            return AlgorithmResult::Done(None);
+        };
+
+        // split across the longer side, a square one either way
+        let (across, down) = chamber.rooms();
+        let vertical = across > down || (across == down && self.rng.random_bool(0.5));
+
+        if vertical {
+            let x = self.random_wall(chamber.left, chamber.right);
+            let gap = self.random_room(chamber.top, chamber.bottom);
+
+            for y in chamber.top..=chamber.bottom {
+                if y != gap {
+                    set_node(grid, &mut changes, (x, y), NodeType::Wall);
+                }
+            }
+
+            self.push(Chamber { left: x + 1, ..chamber });
+            self.push(Chamber { right: x - 1, ..chamber });
+        } else {
+            let y = self.random_wall(chamber.top, chamber.bottom);
+            let gap = self.random_room(chamber.left, chamber.right);
+
+            for x in chamber.left..=chamber.right {
+                if x != gap {
+                    set_node(grid, &mut changes, (x, y), NodeType::Wall);
+                }
+            }
+
+            self.push(Chamber { top: y + 1, ..chamber });
+            self.push(Chamber { bottom: y - 1, ..chamber });
+        }
+
+        AlgorithmResult::ModifiedGrid(changes)
+    }
+
+    fn algorithm_type(&self) -> AlgorithmType {
+        AlgorithmType::MazeGeneration
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{algorithm::{maze::{fill, last_room}, set_node, Algorithm, AlgorithmResult, AlgorithmType, Coord}, grid2d::Grid2D, node::NodeType};
+use rand::prelude::*;
+
+// row by row, rooms are joined into runs going east. once a run ends, one of its rooms opens up to the
+// north. the top row is a single corridor and there's never a dead end facing north
+pub struct Sidewinder {
+    cursor: Coord, // room that's carved next
+    run_start: i32, // first room of the current run
+    right: i32, // last room of a row
+    bottom: i32, // last row of rooms
+    started: bool,
+    rng: StdRng,
+}
+
+impl Sidewinder {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            cursor: (0, 0),
+            run_start: 0,
+            right: 0,
+            bottom: -1,
+            started: false,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Algorithm for Sidewinder {
+    fn step(&mut self, grid: &mut Grid2D) -> AlgorithmResult {
+        let mut changes = Vec::new();
+
+        if !self.started {
+            fill(grid, &mut changes, NodeType::Wall);
+
+            if let Some((right, bottom)) = last_room(grid) {
+                self.right = right;
+                self.bottom = bottom;
+            }
+            self.started = true;
+
+            return AlgorithmResult::ModifiedGrid(changes);
+        }
+
+        let (x, y) = self.cursor;
+        if y > self.bottom {
+            return AlgorithmResult::Done(None);
+        }
+
+        set_node(grid, &mut changes, (x, y), NodeType::Empty);
+
+        // the top row has nothing to the north to open up to, so it's one long run
+        let end_run = y > 0 && (x == self.right || self.rng.random_bool(0.5));
+
+        if end_run {
+            let room = self.run_start + self.rng.random_range(0..=(x - self.run_start) / 2) * 2;
+            set_node(grid, &mut changes, (room, y - 1), NodeType::Empty);
+            self.run_start = x + 2;
+        } else if x < self.right {
+            set_node(grid, &mut changes, (x + 1, y), NodeType::Empty);
+        }
+
+        if x < self.right {
+            self.cursor = (x + 2, y);
+        } else {
+            self.cursor = (0, y + 2);
+            self.run_start = 0;
+        }
+
+        AlgorithmResult::ModifiedGrid(changes)
+    }
+
+    fn highlighted_row(&self) -> Option<i32> {
+        (self.started && self.cursor.1 <= self.bottom).then_some(self.cursor.1)
+    }
+
+    fn algorithm_type(&self) -> AlgorithmType {
+        AlgorithmType::MazeGeneration
+    }
+}
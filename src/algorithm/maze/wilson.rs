@@ -0,0 +1,112 @@
+use crate::{algorithm::{maze::{between, fill, rooms_around}, set_node, Algorithm, AlgorithmResult, AlgorithmType, Coord}, grid2d::Grid2D, node::NodeType};
+use rand::prelude::*;
+
+// random walks from rooms outside the maze until they hit it, with every loop erased on the way,
+// which gives every possible maze the same chance. rooms in the maze are empty, the walk is drawn as one
+pub struct Wilson {
+    walk: Vec<Coord>, // rooms of the current walk, the walls between them are part of it too
+    unvisited: Vec<Coord>, // rooms to start walks from, in random order
+    started: bool,
+    rng: StdRng,
+}
+
+impl Wilson {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            walk: Vec::new(),
+            unvisited: Vec::new(),
+            started: false,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    // the walk's rooms and the walls between them, in order
+    fn walked(&self) -> Vec<Coord> {
+        let mut walked = Vec::new();
+        for (i, &room) in self.walk.iter().enumerate() {
+            if i > 0 {
+                walked.push(between(self.walk[i - 1], room));
+            }
+            walked.push(room);
+        }
+
+        walked
+    }
+}
+
+impl Algorithm for Wilson {
+    fn step(&mut self, grid: &mut Grid2D) -> AlgorithmResult {
+        let mut changes = Vec::new();
+
+        // first step fills the grid and puts a random room in the maze for the walks to find
+        if !self.started {
+            fill(grid, &mut changes, NodeType::Wall);
+
+            for y in (0..grid.height() as i32).step_by(2) {
+                for x in (0..grid.width() as i32).step_by(2) {
+                    self.unvisited.push((x, y));
+                }
+            }
+
+            self.unvisited.shuffle(&mut self.rng);
+            if let Some(first) = self.unvisited.pop() {
+                set_node(grid, &mut changes, first, NodeType::Empty);
+            }
+            self.started = true;
+
+            return AlgorithmResult::ModifiedGrid(changes);
+        }
+
+        let Some(&current) = self.walk.last() else {
+            // start the next walk from a room that isn't in the maze yet
+            while let Some(room) = self.unvisited.pop() {
+                if grid[room].node_type == NodeType::Wall {
+                    set_node(grid, &mut changes, room, NodeType::Walk);
+                    self.walk.push(room);
+
+                    return AlgorithmResult::ModifiedGrid(changes);
+                }
+            }
+
+            return AlgorithmResult::Done(None);
+        };
+
+        // only a grid with a single room has none, and that room is the maze already
+        let Some(&next) = rooms_around(grid, current).choose(&mut self.rng) else {
+            return AlgorithmResult::Done(None);
+        };
+
+        match grid[next].node_type {
+            // the walk reached the maze, so it becomes part of it
+            NodeType::Empty => {
+                for coord in self.walked() {
+                    set_node(grid, &mut changes, coord, NodeType::Empty);
+                }
+                set_node(grid, &mut changes, between(current, next), NodeType::Empty);
+                self.walk.clear();
+            },
+            // the walk crossed itself, everything since it was last here is erased
+            NodeType::Walk => {
+                while let Some(&room) = self.walk.last() && room != next {
+                    self.walk.pop();
+                    set_node(grid, &mut changes, room, NodeType::Wall);
+
+                    if let Some(&previous) = self.walk.last() {
+                        set_node(grid, &mut changes, between(previous, room), NodeType::Wall);
+                    }
+                }
+            },
+            _ => {
+                set_node(grid, &mut changes, between(current, next), NodeType::Walk);
+                set_node(grid, &mut changes, next, NodeType::Walk);
+                self.walk.push(next);
+            },
+        }
+
+        AlgorithmResult::ModifiedGrid(changes)
+    }
+
+    fn algorithm_type(&self) -> AlgorithmType {
+        AlgorithmType::MazeGeneration
+    }
+}
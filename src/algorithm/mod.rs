@@ -1,13 +1,21 @@
-use crate::grid::Node;
+use crate::{grid2d::Grid2D, node::NodeType};
 
 pub mod maze;
 pub mod pathfinding;
 
 pub type Coord = (i32, i32); // (x, y)
 
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct CellChange {
+    pub coord: Coord,
+    pub node_type: NodeType,
+    pub weight: i32,
+    pub label: Option<u32>,
+}
+
 #[derive(PartialEq)]
 pub enum AlgorithmResult {
-    ModifiedGrid,
+    ModifiedGrid(Vec<CellChange>), // every node the step touched
     Done(Option<Vec<Coord>>), // possible path can be returned
     Impossible
 }
@@ -19,7 +27,24 @@ pub enum AlgorithmType {
 }
 
 pub trait Algorithm {
-    fn init(&mut self, start: Coord, end: Coord) { }
-    fn step(&mut self, grid: &mut Vec<Vec<Node>>) -> AlgorithmResult;
+    fn init(&mut self, _start: Coord, _end: Coord) { }
+    fn step(&mut self, grid: &mut Grid2D) -> AlgorithmResult;
     fn algorithm_type(&self) -> AlgorithmType;
+
+    // a row of the grid the algorithm is busy with, e.g. one it's scanning, for the ui to highlight
+    fn highlighted_row(&self) -> Option<i32> {
+        None
+    }
+}
+
+// changes a node and records it, so the ui can show what a step did
+pub fn set_node(grid: &mut Grid2D, changes: &mut Vec<CellChange>, coord: Coord, node_type: NodeType) {
+    grid[coord].set_type(node_type);
+    changes.push(CellChange { coord, node_type, weight: grid[coord].weight, label: grid[coord].label });
+}
+
+// same for a node's label, the type stays as it is
+pub fn set_label(grid: &mut Grid2D, changes: &mut Vec<CellChange>, coord: Coord, label: Option<u32>) {
+    grid[coord].label = label;
+    changes.push(CellChange { coord, node_type: grid[coord].node_type, weight: grid[coord].weight, label });
 }
\ No newline at end of file
@@ -0,0 +1,69 @@
+use std::collections::{ hash_map::Entry, HashMap, VecDeque };
+use crate::{algorithm::{pathfinding::movement::Movement, set_node, Algorithm, AlgorithmResult, AlgorithmType, Coord}, grid2d::Grid2D, node::NodeType};
+
+pub struct Bfs {
+    queue: VecDeque<Coord>,
+    pub parents: HashMap<Coord, Option<Coord>>,
+    pub end_coordinates: Coord,
+    movement: Movement,
+}
+
+// fewest moves, a diagonal counts as one move like any other
+impl Bfs {
+    pub fn new(movement: Movement) -> Self {
+        Self {
+            queue: VecDeque::new(),
+            parents: HashMap::new(),
+            end_coordinates: (0, 0),
+            movement,
+        }
+    }
+
+    fn reconstruct_path(&self) -> Vec<Coord> {
+        let mut path = Vec::new();
+        let mut current = Some(self.end_coordinates);
+
+        while let Some(coord) = current {
+            path.push(coord);
+            current = self.parents.get(&coord).copied().flatten();
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+impl Algorithm for Bfs {
+    fn init(&mut self, start: Coord, end: Coord) {
+        self.end_coordinates = end;
+        self.queue.push_back(start);
+        self.parents.insert(start, None);
+    }
+
+    fn step(&mut self, grid: &mut Grid2D) -> AlgorithmResult {
+        if let Some(curr_coord) = self.queue.pop_front() {
+            if curr_coord == self.end_coordinates {
+                return AlgorithmResult::Done(Some(self.reconstruct_path()));
+            }
+
+            let mut changes = Vec::new();
+            set_node(grid, &mut changes, curr_coord, NodeType::Visited);
+
+            for neighbor in self.movement.neighbors(grid, curr_coord) {
+                // a node is only ever queued once, the first time is the shortest
+                if let Entry::Vacant(entry) = self.parents.entry(neighbor) {
+                    entry.insert(Some(curr_coord));
+                    self.queue.push_back(neighbor);
+                }
+            }
+
+            return AlgorithmResult::ModifiedGrid(changes);
+        }
+
+        AlgorithmResult::Impossible
+    }
+
+    fn algorithm_type(&self) -> AlgorithmType {
+        AlgorithmType::Pathfinding
+    }
+}
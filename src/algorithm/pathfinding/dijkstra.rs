@@ -0,0 +1,103 @@
+use std::{ cmp::Ordering, collections::{ BinaryHeap, HashMap } };
+use crate::{algorithm::{pathfinding::movement::{step_cost, Movement}, set_node, Algorithm, AlgorithmResult, AlgorithmType, Coord}, grid2d::Grid2D, node::NodeType};
+
+struct DijkstraNode {
+    coordinates: Coord,
+    distance: f64,
+}
+
+impl Ord for DijkstraNode {
+    // compare for smaller distance
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance.total_cmp(&self.distance)
+    }
+}
+
+impl PartialEq for DijkstraNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for DijkstraNode {}
+
+impl PartialOrd for DijkstraNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub struct Dijkstra {
+    open_set: BinaryHeap<DijkstraNode>,
+    pub distances: HashMap<Coord, f64>,
+    pub parents: HashMap<Coord, Coord>,
+    pub end_coordinates: Coord,
+    movement: Movement,
+}
+
+impl Dijkstra {
+    pub fn new(movement: Movement) -> Self {
+        Self {
+            open_set: BinaryHeap::new(),
+            distances: HashMap::new(),
+            parents: HashMap::new(),
+            end_coordinates: (0, 0),
+            movement,
+        }
+    }
+
+    fn reconstruct_path(&self) -> Vec<Coord> {
+        let mut path = Vec::new();
+        let mut current = Some(self.end_coordinates);
+
+        while let Some(coord) = current {
+            path.push(coord);
+            current = self.parents.get(&coord).copied();
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+impl Algorithm for Dijkstra {
+    fn init(&mut self, start: Coord, end: Coord) {
+        self.end_coordinates = end;
+        self.open_set.push(DijkstraNode { coordinates: start, distance: 0.0 });
+        self.distances.insert(start, 0.0);
+    }
+
+    fn step(&mut self, grid: &mut Grid2D) -> AlgorithmResult {
+        while let Some(curr_node) = self.open_set.pop() {
+            // a shorter route to this node was already expanded, skip the outdated entry
+            if curr_node.distance > self.distances[&curr_node.coordinates] {
+                continue;
+            }
+
+            if curr_node.coordinates == self.end_coordinates {
+                return AlgorithmResult::Done(Some(self.reconstruct_path()));
+            }
+
+            let mut changes = Vec::new();
+            set_node(grid, &mut changes, curr_node.coordinates, NodeType::Visited);
+
+            for neighbor in self.movement.neighbors(grid, curr_node.coordinates) {
+                let tentative_distance = curr_node.distance + step_cost(grid, curr_node.coordinates, neighbor);
+
+                if tentative_distance < *self.distances.get(&neighbor).unwrap_or(&f64::INFINITY) {
+                    self.distances.insert(neighbor, tentative_distance);
+                    self.parents.insert(neighbor, curr_node.coordinates);
+                    self.open_set.push(DijkstraNode { coordinates: neighbor, distance: tentative_distance });
+                }
+            }
+
+            return AlgorithmResult::ModifiedGrid(changes);
+        }
+
+        AlgorithmResult::Impossible
+    }
+
+    fn algorithm_type(&self) -> AlgorithmType {
+        AlgorithmType::Pathfinding
+    }
+}
@@ -1 +1,18 @@
-pub mod a_star;
\ No newline at end of file
+use crate::algorithm::{pathfinding::{a_star::AStar, bfs::Bfs, dijkstra::Dijkstra, movement::{Heuristic, Movement}}, Algorithm};
+
+pub mod a_star;
+pub mod bfs;
+pub mod dijkstra;
+pub mod movement;
+
+pub const NAMES: [&str; 3] = ["astar", "bfs", "dijkstra"];
+
+// the heuristic only matters to A*
+pub fn from_name(name: &str, movement: Movement, heuristic: Heuristic) -> Option<Box<dyn Algorithm>> {
+    match name {
+        "astar" => Some(Box::new(AStar::new(movement, heuristic))),
+        "bfs" => Some(Box::new(Bfs::new(movement))),
+        "dijkstra" => Some(Box::new(Dijkstra::new(movement))),
+        _ => None,
+    }
+}
@@ -0,0 +1,110 @@
+use std::f64::consts::SQRT_2;
+
+use crate::{algorithm::Coord, grid2d::{Connectivity, Grid2D}, node::NodeType};
+
+// which moves a pathfinder may make between nodes
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Movement {
+    FourWay,
+    EightWay,
+    EightWayNoCornerCutting, // diagonals only when both nodes beside the move are open
+}
+
+impl Movement {
+    // "4", "8" or "8-no-corners", as typed on the command line
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "4" => Some(Self::FourWay),
+            "8" => Some(Self::EightWay),
+            "8-no-corners" => Some(Self::EightWayNoCornerCutting),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::FourWay => "4-way",
+            Self::EightWay => "8-way",
+            Self::EightWayNoCornerCutting => "8-way, no corner cutting",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Self::FourWay => Self::EightWay,
+            Self::EightWay => Self::EightWayNoCornerCutting,
+            Self::EightWayNoCornerCutting => Self::FourWay,
+        }
+    }
+
+    // the heuristic that's exact on an empty grid with these moves
+    pub fn heuristic(self) -> Heuristic {
+        match self {
+            Self::FourWay => Heuristic::Manhattan,
+            Self::EightWay | Self::EightWayNoCornerCutting => Heuristic::Octile,
+        }
+    }
+
+    // open nodes that can be reached from `coord` in one move
+    pub fn neighbors(self, grid: &Grid2D, coord: Coord) -> Vec<Coord> {
+        let connectivity = if self == Self::FourWay { Connectivity::Four } else { Connectivity::Eight };
+        let is_open = |coord: Coord| grid.get(coord).is_some_and(|node| node.node_type != NodeType::Wall);
+
+        grid.neighbors(coord, connectivity)
+            .filter(|&neighbor| is_open(neighbor))
+            .filter(|&neighbor| {
+                // no squeezing past the corner of a wall
+                let diagonal = neighbor.0 != coord.0 && neighbor.1 != coord.1;
+                self != Self::EightWayNoCornerCutting || !diagonal
+                    || (is_open((neighbor.0, coord.1)) && is_open((coord.0, neighbor.1)))
+            })
+            .collect()
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Heuristic {
+    Manhattan,
+    Octile,
+    Chebyshev,
+    Euclidean,
+}
+
+impl Heuristic {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "manhattan" => Some(Self::Manhattan),
+            "octile" => Some(Self::Octile),
+            "chebyshev" => Some(Self::Chebyshev),
+            "euclidean" => Some(Self::Euclidean),
+            _ => None,
+        }
+    }
+
+    pub fn estimate(self, from: Coord, to: Coord) -> f64 {
+        let dx = from.0.abs_diff(to.0) as f64;
+        let dy = from.1.abs_diff(to.1) as f64;
+
+        match self {
+            Self::Manhattan => dx + dy,
+            Self::Octile => dx.max(dy) + (SQRT_2 - 1.0) * dx.min(dy),
+            Self::Chebyshev => dx.max(dy),
+            Self::Euclidean => dx.hypot(dy),
+        }
+    }
+}
+
+// length of a single move, diagonals are √2
+pub fn step_length(from: Coord, to: Coord) -> f64 {
+    if from.0 != to.0 && from.1 != to.1 { SQRT_2 } else { 1.0 }
+}
+
+// cost of moving onto `to`, the node's weight scaled by how far the move goes
+pub fn step_cost(grid: &Grid2D, from: Coord, to: Coord) -> f64 {
+    step_length(from, to) * grid[to].weight as f64
+}
+
+// what following a path costs, stepping off the start is free
+pub fn path_cost(grid: &Grid2D, path: &[Coord]) -> f64 {
+    path.windows(2).map(|step| step_cost(grid, step[0], step[1])).sum()
+}
@@ -1,13 +1,22 @@
-use std::{io, rc::Rc, sync::mpsc::{Receiver, Sender}};
+use std::{cell::RefCell, io, path::PathBuf, rc::Rc, sync::mpsc::{Receiver, RecvTimeoutError}, time::Instant};
 
+use crossterm::event::MouseButton;
 use ratatui::DefaultTerminal;
 
-use crate::{algorithm::{AlgorithmResult, AlgorithmType}, event::{handle_key_press, Event}, grid::{Grid, GridState, Node, NodeType}, sidebar::Sidebar, ui::draw, utils::abs_to_grid};
+use mavis::{algorithm::{Algorithm, AlgorithmResult, AlgorithmType, CellChange}, grid2d::Grid2D, maze_file, node::{Node, NodeType}};
+
+use crate::{event::{handle_key_press, handle_mouse_paint, Event}, grid::{Grid, GridState}, history::History, sidebar::Sidebar, speed::{Speed, FRAME_TIME}, ui::draw};
 
 pub struct App {
     pub exit: bool,
     pub sidebar: Sidebar,
     pub grid: Grid,
+    pub speed: Speed,
+    pub load_path: PathBuf,
+    pub save_path: PathBuf,
+    pub status: Option<String>,
+    pub fixed_seed: Option<u64>, // every maze is generated from this, random seeds if it's None
+    pub seed_input: Option<String>, // what's been typed so far while setting the seed
 }
 
 impl App {
@@ -16,92 +25,71 @@ impl App {
             exit: false,
             sidebar: Sidebar::new(),
             grid: Grid::new(),
+            speed: Speed::new(),
+            load_path: PathBuf::from("maze.txt"),
+            save_path: PathBuf::from("maze.txt"),
+            status: None,
+            fixed_seed: None,
+            seed_input: None,
         }
     }
 
-    pub fn run(&mut self, terminal: &mut DefaultTerminal, rx: Receiver<Event>, tx: Sender<Event>) -> io::Result<()> {
-        let mut tick = 0;
+    pub fn run(&mut self, terminal: &mut DefaultTerminal, rx: Receiver<Event>, load: Option<PathBuf>) -> io::Result<()> {
+        // the grid only gets its size on the first draw, so load afterwards
+        terminal.draw(|frame| draw(self, frame))?;
+        if let Some(path) = load {
+            self.load_path = path;
+            self.load_maze();
+        }
+
+        let mut last_draw = Instant::now();
         while !self.exit {
             match &mut self.grid.state {
                 GridState::Generating(algorithm) => {
-                    if self.grid.clear && algorithm.borrow().algorithm_type() == AlgorithmType::MazeGeneration {
-                        for row in &mut self.grid.content {
-                            for node in row {
-                                node.node_type = NodeType::Empty;
-                            }
-                        }
-
-                        // reset vars
-                        self.grid.iter_count = 0;
-                        self.grid.clear = false;
-                    }
-
-                    let tick_diff = match algorithm.borrow().algorithm_type() {
-                        AlgorithmType::MazeGeneration => 50,
-                        AlgorithmType::Pathfinding => 15
-                    };
+                    let algorithm = Rc::clone(algorithm);
 
-                    let curr_step = algorithm.borrow_mut().step(&mut self.grid.content);
-                    self.grid.iter_count += 1;
-                    if matches!(curr_step, AlgorithmResult::Done(_)) || matches!(curr_step, AlgorithmResult::Impossible) {
-                        self.grid.state = GridState::Idle;
-                        self.grid.clear = true;
-
-                        if let AlgorithmResult::Done(Some(path)) = curr_step {
-                            for coord in path {
-                                self.grid.content[coord.1 as usize][coord.0 as usize] = Node { node_type: NodeType::Path };
-                            }
-
-                            tick = 0; // to make the terminal draw when it's done
-                        }
-
-                        // clear markers
-                        if let Some(_) = self.grid.markers.start {
-                            self.grid.markers.start = None;
-                            self.grid.markers.end = None;
+                    for _ in 0..self.speed.steps_due() {
+                        if !self.step(&algorithm) {
+                            break;
                         }
                     }
 
-                    if tick % tick_diff == 0 {
+                    if last_draw.elapsed() >= FRAME_TIME || !matches!(self.grid.state, GridState::Generating(_)) {
                         terminal.draw(|frame| draw(self, frame))?;
+                        last_draw = Instant::now();
                     }
 
-                    tick += 1;
+                    // wait for the next step, reading input in the meantime so the run can be paused
+                    match rx.recv_timeout(self.speed.until_next_step().min(FRAME_TIME)) {
+                        Ok(Event::KeyPress(key_code, modifiers)) => handle_key_press(self, key_code, modifiers),
+                        Ok(_) | Err(RecvTimeoutError::Timeout) => {},
+                        Err(e) => return Err(io::Error::other(e)),
+                    }
                 },
-                GridState::PlacingMarkers(algorithm) => {
-                    // hasn't placed anything yet, just skip to render terminal.
-                    if self.grid.markers.start == None {
-                        tx.send(Event::Empty).expect("Should be able to send empty event.");
-                        tick = 0;
+                GridState::Paused(_) => {
+                    terminal.draw(|frame| draw(self, frame))?;
+                    if let Event::KeyPress(key_code, modifiers) = rx.recv().map_err(io::Error::other)? {
+                        handle_key_press(self, key_code, modifiers);
                     }
+                },
+                GridState::PlacingMarkers(algorithm) => {
+                    let algorithm = Rc::clone(algorithm);
 
-                    match rx.recv().map_err(|e| io::Error::new(io::ErrorKind::Other, e))? {
-                        Event::MousePress(position) => {
-                            let Some(grid_start) = self.grid.grid_start else {
-                                panic!("Grid should be initialized");
-                            };
-
-                            let Some(grid_end) = self.grid.grid_end else {
-                                panic!("Grid should be initialized");
-                            };
-
+                    terminal.draw(|frame| draw(self, frame))?;
+                    match rx.recv().map_err(io::Error::other)? {
+                        Event::MousePress(MouseButton::Left, position) => {
                             // out of bounds.
-                            if position.0 < grid_start.0 || position.0 > grid_end.0 || position.1 < grid_start.1 || position.1 > grid_end.1 {
+                            let Some(position) = self.grid.screen_to_grid(position) else {
                                 continue;
-                            }
+                            };
 
-                            if self.grid.markers.start == None {
+                            if self.grid.markers.start.is_none() {
                                 self.grid.markers.start = Some(position);
 
-                                for row in &mut self.grid.content {
-                                    for node in row {
-                                        if node.node_type != NodeType::Wall && node.node_type != NodeType::Empty {
-                                            node.node_type = NodeType::Empty;
-                                        }
-                                    }
-                                }
+                                self.grid.content.iter_mut().for_each(Node::clear_overlay);
 
                                 self.grid.iter_count = 0;
+                                self.grid.history = History::new();
                             } else {
                                 self.grid.markers.end = Some(position);
 
@@ -109,30 +97,139 @@ impl App {
                                     panic!("Start should be valid");
                                 };
 
-                                let new_algo = Rc::clone(algorithm);
-                                new_algo.borrow_mut().init(abs_to_grid(start, grid_start), abs_to_grid(position, grid_start));
+                                algorithm.borrow_mut().init(start, position);
 
-                                self.grid.state = GridState::Generating(new_algo);
+                                self.grid.state = GridState::Generating(algorithm);
                             }
-                        }
+                        },
+                        Event::KeyPress(key_code, modifiers) => handle_key_press(self, key_code, modifiers),
                         _ => {},
-                    };
-
-                    if tick % 10 == 0 {
-                        terminal.draw(|frame| draw(self, frame))?;
                     }
-                    tick += 1;
                 },
                 GridState::Idle => {
                     terminal.draw(|frame| draw(self, frame))?;
-                    match rx.recv().map_err(|e| io::Error::new(io::ErrorKind::Other, e))? {
-                        Event::KeyPress(key_code) => handle_key_press(self, key_code),
-                        _ => {},
-                    };
+                    match rx.recv().map_err(io::Error::other)? {
+                        Event::KeyPress(key_code, modifiers) => handle_key_press(self, key_code, modifiers),
+                        Event::MousePress(button, position) | Event::MouseDrag(button, position) => handle_mouse_paint(self, button, position),
+                        Event::MouseRelease(button, position) => {
+                            handle_mouse_paint(self, button, position);
+                            self.grid.last_paint = None;
+                        },
+                    }
                 },
             }
         }
 
         Ok(())
     }
+
+    // runs a single step of the algorithm, returns false once it has finished
+    fn step(&mut self, algorithm: &Rc<RefCell<dyn Algorithm>>) -> bool {
+        // nothing to run on until the window was big enough to give the grid a size
+        if self.grid.content.is_empty() {
+            self.grid.state = GridState::Idle;
+            return false;
+        }
+
+        self.grid.scrub_to_end();
+
+        if self.grid.clear && algorithm.borrow().algorithm_type() == AlgorithmType::MazeGeneration {
+            self.grid.content = Grid2D::new(self.grid.width(), self.grid.height());
+
+            // reset vars
+            self.grid.iter_count = 0;
+            self.grid.clear = false;
+        }
+
+        // a fresh run, start recording from the grid as it is now
+        if self.grid.iter_count == 0 {
+            self.grid.history = History::start(&self.grid.content, self.grid.markers);
+        }
+
+        let curr_step = algorithm.borrow_mut().step(&mut self.grid.content);
+        self.grid.iter_count += 1;
+        if let AlgorithmResult::ModifiedGrid(changes) = curr_step {
+            self.grid.history.record(self.grid.iter_count, &changes);
+            self.grid.last_changes = changes;
+            return true;
+        }
+
+        self.grid.state = GridState::Idle;
+        self.grid.clear = true;
+        self.grid.last_changes.clear();
+
+        if let AlgorithmResult::Done(Some(path)) = curr_step {
+            for coord in path {
+                self.grid.content[coord].node_type = NodeType::Path;
+                let node = &self.grid.content[coord];
+                self.grid.last_changes.push(CellChange { coord, node_type: NodeType::Path, weight: node.weight, label: node.label });
+            }
+        }
+
+        self.grid.history.record(self.grid.iter_count, &self.grid.last_changes);
+
+        // clear markers
+        if self.grid.markers.start.is_some() {
+            self.grid.markers.start = None;
+            self.grid.markers.end = None;
+        }
+
+        false
+    }
+
+    // advances a paused algorithm by exactly one step, or replays the
+    // next recorded one if the grid has been rewound
+    pub fn step_once(&mut self) {
+        if !self.grid.history.at_end() {
+            self.grid.scrub_forward();
+        } else if let GridState::Paused(algorithm) = &self.grid.state {
+            let algorithm = Rc::clone(algorithm);
+            self.step(&algorithm);
+        }
+    }
+
+    pub fn save_maze(&mut self) {
+        // fall back to the markers of the last run, those are cleared once it's done
+        let markers = if self.grid.markers.start.is_some() { self.grid.markers } else { self.grid.history.markers };
+
+        self.status = Some(match maze_file::save(&self.save_path, &self.grid.content, markers.start, markers.end) {
+            Ok(()) => format!("Saved maze to {}", self.save_path.display()),
+            Err(e) => format!("Couldn't save {}: {}", self.save_path.display(), e),
+        });
+    }
+
+    pub fn edit_seed(&mut self) {
+        self.seed_input = Some(self.fixed_seed.map_or(String::new(), |seed| seed.to_string()));
+    }
+
+    pub fn confirm_seed(&mut self) {
+        let Some(input) = self.seed_input.take() else {
+            return;
+        };
+
+        self.status = Some(if input.is_empty() {
+            self.fixed_seed = None;
+            String::from("Mazes use a random seed")
+        } else {
+            match input.parse() {
+                Ok(seed) => {
+                    self.fixed_seed = Some(seed);
+                    format!("Mazes use seed {}", seed)
+                },
+                Err(_) => format!("Invalid seed \"{}\"", input),
+            }
+        });
+    }
+
+    pub fn load_maze(&mut self) {
+        self.status = Some(match maze_file::load(&self.load_path) {
+            Ok(maze) => {
+                let size = (maze.width(), maze.height());
+                self.grid.load(maze);
+
+                format!("Loaded {} ({} x {})", self.load_path.display(), size.0, size.1)
+            },
+            Err(e) => format!("Couldn't load {}: {}", self.load_path.display(), e),
+        });
+    }
 }
\ No newline at end of file
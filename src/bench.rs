@@ -0,0 +1,208 @@
+use std::time::Duration;
+
+use mavis::{algorithm::{maze, pathfinding}, grid2d::Grid2D, runner::{corner_markers, run_to_completion, solve}};
+
+use crate::cli::{BenchArgs, Format};
+
+// the solver every path is compared against, it's optimal with weights too
+const REFERENCE: &str = "dijkstra";
+
+// every run of one solver on one generator's mazes
+struct Runs {
+    maze: &'static str,
+    solver: &'static str,
+    runs: usize,
+    visited: Vec<f64>,
+    path_lengths: Vec<f64>,
+    cost_ratios: Vec<f64>, // path cost over the reference's, 1 is optimal
+    steps: u64,
+    elapsed: Duration,
+}
+
+impl Runs {
+    fn new(maze: &'static str, solver: &'static str) -> Self {
+        Self {
+            maze,
+            solver,
+            runs: 0,
+            visited: Vec::new(),
+            path_lengths: Vec::new(),
+            cost_ratios: Vec::new(),
+            steps: 0,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    fn solved(&self) -> usize {
+        self.path_lengths.len()
+    }
+
+    fn optimal(&self) -> usize {
+        // diagonal costs are sums of √2, so equal paths can differ in the last bits
+        self.cost_ratios.iter().filter(|&&ratio| ratio <= 1.0 + 1e-9).count()
+    }
+
+    fn nanos_per_step(&self) -> f64 {
+        self.elapsed.as_nanos() as f64 / self.steps.max(1) as f64
+    }
+}
+
+// `mavis bench`, solves every generator's mazes with every pathfinder and prints the stats
+pub fn run(args: BenchArgs) -> Result<(), String> {
+    let (width, height) = args.size;
+    let seed = args.seed.unwrap_or_else(rand::random);
+
+    // the results can go to a file, so this goes to stderr
+    eprintln!(
+        "bench: {} mazes of {} x {} per generator, seeds {}..{}, {} moves",
+        args.mazes, width, height, seed, seed.wrapping_add(args.mazes as u64), args.movement.name(),
+    );
+
+    let mut results = Vec::new();
+    for maze_name in maze::NAMES {
+        let mut runs: Vec<Runs> = pathfinding::NAMES.iter().map(|&solver| Runs::new(maze_name, solver)).collect();
+
+        for i in 0..args.mazes {
+            let mut grid = Grid2D::new(width, height);
+            let mut generator = maze::from_name(maze_name, seed.wrapping_add(i as u64)).ok_or("unknown maze")?;
+            run_to_completion(generator.as_mut(), &mut grid);
+
+            let Some((start, end)) = corner_markers(&grid) else {
+                continue;
+            };
+
+            let mut reference = pathfinding::from_name(REFERENCE, args.movement, args.heuristic).ok_or("unknown solver")?;
+            let reference = solve(&grid, reference.as_mut(), start, end);
+
+            for solver_runs in &mut runs {
+                let mut solver = pathfinding::from_name(solver_runs.solver, args.movement, args.heuristic).ok_or("unknown solver")?;
+                let solution = solve(&grid, solver.as_mut(), start, end);
+
+                solver_runs.runs += 1;
+                solver_runs.visited.push(solution.visited as f64);
+                solver_runs.steps += solution.steps;
+                solver_runs.elapsed += solution.elapsed;
+
+                if let Some(path) = &solution.path {
+                    solver_runs.path_lengths.push((path.len() - 1) as f64);
+
+                    // a start right next to the end costs nothing either way
+                    let ratio = if reference.cost == 0.0 { 1.0 } else { solution.cost / reference.cost };
+                    solver_runs.cost_ratios.push(ratio);
+                }
+            }
+        }
+
+        results.append(&mut runs);
+    }
+
+    match args.format {
+        Format::Table => print_table(&results),
+        Format::Csv => print_csv(&results),
+        Format::Json => print_json(&results),
+    }
+
+    Ok(())
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+
+    Some(values.iter().sum::<f64>() / values.len() as f64)
+}
+
+// nearest rank, p is between 0 and 100
+fn percentile(values: &[f64], p: usize) -> Option<f64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied()
+}
+
+// one row of numbers per solver and generator, None where nothing was solved
+fn columns(runs: &Runs) -> [(&'static str, Option<f64>); 10] {
+    [
+        ("runs", Some(runs.runs as f64)),
+        ("solved", Some(runs.solved() as f64)),
+        ("visited_mean", mean(&runs.visited)),
+        ("visited_p50", percentile(&runs.visited, 50)),
+        ("visited_p90", percentile(&runs.visited, 90)),
+        ("path_mean", mean(&runs.path_lengths)),
+        ("path_p90", percentile(&runs.path_lengths, 90)),
+        ("optimal", (runs.solved() > 0).then(|| runs.optimal() as f64)),
+        ("cost_ratio_mean", mean(&runs.cost_ratios)),
+        ("ns_per_step", Some(runs.nanos_per_step())),
+    ]
+}
+
+fn print_table(results: &[Runs]) {
+    println!(
+        "{:<14} {:<9} {:>6} {:>9} {:>9} {:>9} {:>9} {:>9} {:>9} {:>9} {:>9}",
+        "maze", "solver", "solved", "visited", "p50", "p90", "path", "p90", "optimal", "cost", "ns/step",
+    );
+
+    for runs in results {
+        let [_, _, visited_mean, visited_p50, visited_p90, path_mean, path_p90, optimal, cost_ratio, ns_per_step] = columns(runs)
+            .map(|(_, value)| value);
+        let cell = |value: Option<f64>, decimals: usize| value.map_or(String::from("-"), |value| format!("{:.*}", decimals, value));
+
+        println!(
+            "{:<14} {:<9} {:>6} {:>9} {:>9} {:>9} {:>9} {:>9} {:>9} {:>9} {:>9}",
+            runs.maze,
+            runs.solver,
+            format!("{}/{}", runs.solved(), runs.runs),
+            cell(visited_mean, 1),
+            cell(visited_p50, 0),
+            cell(visited_p90, 0),
+            cell(path_mean, 1),
+            cell(path_p90, 0),
+            optimal.map_or(String::from("-"), |optimal| format!("{}/{}", optimal, runs.solved())),
+            cell(cost_ratio, 3),
+            cell(ns_per_step, 0),
+        );
+    }
+}
+
+fn print_csv(results: &[Runs]) {
+    let names = columns(&results[0]).map(|(name, _)| name);
+    println!("maze,solver,{}", names.join(","));
+
+    for runs in results {
+        let values = columns(runs).map(|(_, value)| value.map_or(String::new(), |value| value.to_string()));
+        println!("{},{},{}", runs.maze, runs.solver, values.join(","));
+    }
+}
+
+fn print_json(results: &[Runs]) {
+    let rows: Vec<String> = results.iter().map(|runs| {
+        let fields: Vec<String> = columns(runs).iter()
+            .map(|(name, value)| format!("\"{}\": {}", name, value.map_or(String::from("null"), |value| value.to_string())))
+            .collect();
+
+        format!("  {{\"maze\": \"{}\", \"solver\": \"{}\", {}}}", runs.maze, runs.solver, fields.join(", "))
+    }).collect();
+
+    println!("[\n{}\n]", rows.join(",\n"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_is_the_nearest_rank() {
+        let values = [5.0, 1.0, 4.0, 2.0, 3.0];
+
+        assert_eq!(percentile(&values, 0), Some(1.0));
+        assert_eq!(percentile(&values, 20), Some(1.0));
+        assert_eq!(percentile(&values, 21), Some(2.0));
+        assert_eq!(percentile(&values, 50), Some(3.0));
+        assert_eq!(percentile(&values, 90), Some(5.0));
+        assert_eq!(percentile(&values, 100), Some(5.0));
+        assert_eq!(percentile(&[7.0], 90), Some(7.0));
+        assert_eq!(percentile(&[], 50), None);
+    }
+}
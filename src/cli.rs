@@ -0,0 +1,200 @@
+use std::path::PathBuf;
+
+use mavis::algorithm::pathfinding::movement::{Heuristic, Movement};
+
+pub const USAGE: &str = "\
+Usage: mavis [--load <FILE>] [--save <FILE>] [--size <WIDTH>x<HEIGHT>] [--seed <SEED>]
+       mavis run [--maze <MAZE> | --load <FILE>] [--solver <SOLVER>] [--movement <MOVES>] [--heuristic <HEURISTIC>]
+                 [--size <WIDTH>x<HEIGHT>] [--seed <SEED>]
+       mavis bench [--mazes <N>] [--movement <MOVES>] [--heuristic <HEURISTIC>] [--size <WIDTH>x<HEIGHT>] [--seed <SEED>]
+                   [--format <FORMAT>]
+
+Options:
+  --load <FILE>  load a maze from FILE on startup, also used by the sidebar's \"Load Maze\"
+  --save <FILE>  file the sidebar's \"Save Maze\" writes to (default: maze.txt)
+  --size <WxH>   size of the grid, defaults to whatever fits the terminal
+  --seed <SEED>  generate every maze from SEED instead of a random one
+
+Run options (no terminal ui, prints the results):
+  --maze <MAZE>      generator for the maze: backtracker, prims, kruskal, wilson, aldous-broder, hunt-and-kill,
+                     division, eller, sidewinder, binary-tree or noise:<FILL>[:<MUD>] (default: an empty grid)
+  --load <FILE>      solve a saved maze instead, its markers are used as start and end
+  --solver <SOLVER>  astar (default), bfs or dijkstra
+  --movement <M>     4 (default), 8 or 8-no-corners, the last one can't cut past the corner of a wall
+  --heuristic <H>    A*'s estimate: manhattan, octile, chebyshev or euclidean (default: manhattan for 4, octile for 8)
+  --size <WxH>       size of the generated maze (default: 100x50)
+  --seed <SEED>      seed for the generated maze (default: random, the one used is printed)
+
+Bench options (every generator's mazes solved by every pathfinder):
+  --mazes <N>        mazes per generator (default: 20)
+  --movement <M>     how every pathfinder moves, as for run (default: 4)
+  --heuristic <H>    A*'s estimate, as for run
+  --size <WxH>       size of the mazes (default: 101x51)
+  --seed <SEED>      seed of the first maze, the next ones count up from there (default: random)
+  --format <FORMAT>  table (default), csv or json";
+
+pub enum Command {
+    Tui(Args),
+    Run(RunArgs),
+    Bench(BenchArgs),
+}
+
+pub struct Args {
+    pub load: Option<PathBuf>,
+    pub save: Option<PathBuf>,
+    pub size: Option<(usize, usize)>,
+    pub seed: Option<u64>,
+}
+
+pub struct RunArgs {
+    pub maze: Option<String>,
+    pub load: Option<PathBuf>,
+    pub solver: String,
+    pub movement: Movement,
+    pub heuristic: Heuristic,
+    pub size: (usize, usize),
+    pub seed: Option<u64>,
+}
+
+pub struct BenchArgs {
+    pub mazes: usize,
+    pub movement: Movement,
+    pub heuristic: Heuristic,
+    pub size: (usize, usize),
+    pub seed: Option<u64>,
+    pub format: Format,
+}
+
+pub enum Format {
+    Table,
+    Csv,
+    Json,
+}
+
+pub fn parse(args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut args = args.skip(1).peekable();
+
+    match args.peek().map(String::as_str) {
+        Some("run") => return parse_run(args.skip(1)).map(Command::Run),
+        Some("bench") => return parse_bench(args.skip(1)).map(Command::Bench),
+        _ => {},
+    }
+
+    let mut parsed = Args {
+        load: None,
+        save: None,
+        size: None,
+        seed: None,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--load" => parsed.load = Some(value(&arg, args.next())?.into()),
+            "--save" => parsed.save = Some(value(&arg, args.next())?.into()),
+            "--size" => parsed.size = Some(parse_size(&value(&arg, args.next())?)?),
+            "--seed" => parsed.seed = Some(parse_seed(&value(&arg, args.next())?)?),
+            _ => return Err(format!("unknown argument \"{}\"", arg)),
+        }
+    }
+
+    Ok(Command::Tui(parsed))
+}
+
+fn parse_run(mut args: impl Iterator<Item = String>) -> Result<RunArgs, String> {
+    let mut parsed = RunArgs {
+        maze: None,
+        load: None,
+        solver: String::from("astar"),
+        movement: Movement::FourWay,
+        heuristic: Heuristic::Manhattan,
+        size: (100, 50),
+        seed: None,
+    };
+    let mut heuristic = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--maze" => parsed.maze = Some(value(&arg, args.next())?),
+            "--load" => parsed.load = Some(value(&arg, args.next())?.into()),
+            "--solver" => parsed.solver = value(&arg, args.next())?,
+            "--movement" => parsed.movement = parse_movement(&value(&arg, args.next())?)?,
+            "--heuristic" => heuristic = Some(parse_heuristic(&value(&arg, args.next())?)?),
+            "--size" => parsed.size = parse_size(&value(&arg, args.next())?)?,
+            "--seed" => parsed.seed = Some(parse_seed(&value(&arg, args.next())?)?),
+            _ => return Err(format!("unknown argument \"{}\"", arg)),
+        }
+    }
+
+    if parsed.maze.is_some() && parsed.load.is_some() {
+        return Err(String::from("--maze and --load can't be used together"));
+    }
+
+    parsed.heuristic = heuristic.unwrap_or(parsed.movement.heuristic());
+    Ok(parsed)
+}
+
+fn parse_bench(mut args: impl Iterator<Item = String>) -> Result<BenchArgs, String> {
+    let mut parsed = BenchArgs {
+        mazes: 20,
+        movement: Movement::FourWay,
+        heuristic: Heuristic::Manhattan,
+        size: (101, 51),
+        seed: None,
+        format: Format::Table,
+    };
+    let mut heuristic = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--mazes" => {
+                let mazes = value(&arg, args.next())?;
+                parsed.mazes = mazes.parse().ok().filter(|&mazes| mazes > 0)
+                    .ok_or_else(|| format!("invalid number of mazes \"{}\"", mazes))?;
+            },
+            "--movement" => parsed.movement = parse_movement(&value(&arg, args.next())?)?,
+            "--heuristic" => heuristic = Some(parse_heuristic(&value(&arg, args.next())?)?),
+            "--size" => parsed.size = parse_size(&value(&arg, args.next())?)?,
+            "--seed" => parsed.seed = Some(parse_seed(&value(&arg, args.next())?)?),
+            "--format" => parsed.format = match value(&arg, args.next())?.as_str() {
+                "table" => Format::Table,
+                "csv" => Format::Csv,
+                "json" => Format::Json,
+                format => return Err(format!("unknown format \"{}\", expected table, csv or json", format)),
+            },
+            _ => return Err(format!("unknown argument \"{}\"", arg)),
+        }
+    }
+
+    parsed.heuristic = heuristic.unwrap_or(parsed.movement.heuristic());
+    Ok(parsed)
+}
+
+fn value(flag: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("{} expects a value", flag))
+}
+
+// "200x100" -> (200, 100)
+fn parse_size(size: &str) -> Result<(usize, usize), String> {
+    let parsed = size.split_once('x').and_then(|(width, height)| {
+        Some((width.parse::<usize>().ok()?, height.parse::<usize>().ok()?))
+    });
+
+    // a grid that big couldn't even be counted, let alone allocated
+    match parsed {
+        Some((width, height)) if width > 0 && height > 0 && width.checked_mul(height).is_some() => Ok((width, height)),
+        _ => Err(format!("invalid size \"{}\", expected something like 200x100", size)),
+    }
+}
+
+fn parse_seed(seed: &str) -> Result<u64, String> {
+    seed.parse().map_err(|_| format!("invalid seed \"{}\", expected a whole number", seed))
+}
+
+fn parse_movement(movement: &str) -> Result<Movement, String> {
+    Movement::from_name(movement).ok_or_else(|| format!("unknown movement \"{}\", expected 4, 8 or 8-no-corners", movement))
+}
+
+fn parse_heuristic(heuristic: &str) -> Result<Heuristic, String> {
+    Heuristic::from_name(heuristic)
+        .ok_or_else(|| format!("unknown heuristic \"{}\", expected manhattan, octile, chebyshev or euclidean", heuristic))
+}
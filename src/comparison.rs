@@ -0,0 +1,109 @@
+use mavis::{algorithm::{pathfinding::movement::path_cost, Algorithm, AlgorithmResult, AlgorithmType, CellChange, Coord}, grid2d::Grid2D, node::{Node, NodeType}};
+
+// how a pane's run ended
+pub struct Summary {
+    pub path: Option<(usize, f64)>, // moves and cost
+    pub visited: usize,
+}
+
+// one pathfinder running on its own copy of the maze
+pub struct Pane {
+    pub name: &'static str,
+    pub algorithm: Box<dyn Algorithm>,
+    pub content: Grid2D,
+    pub iter_count: i32,
+    pub last_changes: Vec<CellChange>,
+    pub summary: Option<Summary>,
+}
+
+impl Pane {
+    fn new(name: &'static str, algorithm: Box<dyn Algorithm>) -> Self {
+        Self {
+            name,
+            algorithm,
+            content: Grid2D::new(0, 0),
+            iter_count: 0,
+            last_changes: Vec::new(),
+            summary: None,
+        }
+    }
+
+    fn step(&mut self) {
+        let result = self.algorithm.step(&mut self.content);
+        self.iter_count += 1;
+
+        let path = match result {
+            AlgorithmResult::ModifiedGrid(changes) => {
+                self.last_changes = changes;
+                return;
+            },
+            AlgorithmResult::Done(path) => path,
+            AlgorithmResult::Impossible => None,
+        };
+
+        self.last_changes.clear();
+        let visited = self.content.iter().filter(|node| node.node_type == NodeType::Visited).count();
+
+        let path = path.map(|path| {
+            for &coord in &path {
+                self.content[coord].node_type = NodeType::Path;
+            }
+
+            (path.len() - 1, path_cost(&self.content, &path))
+        });
+
+        self.summary = Some(Summary { path, visited });
+    }
+}
+
+// several pathfinders racing on the same maze. it's an algorithm itself, so it's paced,
+// paused and given its markers like any other one, while the panes keep their own grids
+pub struct Comparison {
+    pub panes: Vec<Pane>,
+    started: bool,
+}
+
+impl Comparison {
+    pub fn new(panes: Vec<(&'static str, Box<dyn Algorithm>)>) -> Self {
+        Self {
+            panes: panes.into_iter().map(|(name, algorithm)| Pane::new(name, algorithm)).collect(),
+            started: false,
+        }
+    }
+}
+
+impl Algorithm for Comparison {
+    fn init(&mut self, start: Coord, end: Coord) {
+        for pane in &mut self.panes {
+            pane.algorithm.init(start, end);
+        }
+    }
+
+    // the maze itself is left alone, every pane works on a copy of it
+    fn step(&mut self, grid: &mut Grid2D) -> AlgorithmResult {
+        if !self.started {
+            for pane in &mut self.panes {
+                pane.content = grid.clone();
+                pane.content.iter_mut().for_each(Node::clear_overlay);
+            }
+
+            self.started = true;
+        }
+
+        for pane in &mut self.panes {
+            if pane.summary.is_none() {
+                pane.step();
+            }
+        }
+
+        if self.panes.iter().all(|pane| pane.summary.is_some()) {
+            AlgorithmResult::Done(None)
+        } else {
+            AlgorithmResult::ModifiedGrid(Vec::new())
+        }
+    }
+
+    fn algorithm_type(&self) -> AlgorithmType {
+        AlgorithmType::Pathfinding
+    }
+}
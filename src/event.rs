@@ -1,29 +1,38 @@
 use std::{ io, sync::mpsc::Sender };
 
-use crossterm::event::{ KeyCode, KeyEventKind, MouseButton, MouseEventKind };
+use crossterm::event::{ KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind };
 
-use crate::{algorithm::Coord, app::App};
+use mavis::{algorithm::Coord, node::NodeType};
+
+use crate::{app::App, grid::GridState, sidebar::AppAction};
 
 pub enum Event {
-    KeyPress(KeyCode),
-    MousePress(Coord),
-    Empty
+    KeyPress(KeyCode, KeyModifiers),
+    MousePress(MouseButton, Coord),
+    MouseDrag(MouseButton, Coord),
+    MouseRelease(MouseButton, Coord),
 }
 
 pub fn loop_key_events(tx: Sender<Event>) -> io::Result<()> {
     loop {
         match crossterm::event::read()? {
-            crossterm::event::Event::Key(key_event) => {
-                if key_event.kind == KeyEventKind::Press {
-                    tx.send(Event::KeyPress(key_event.code)).expect(
-                        "Should be able to send key press event to receiver."
-                    );
-                }
+            crossterm::event::Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
+                tx.send(Event::KeyPress(key_event.code, key_event.modifiers)).expect(
+                    "Should be able to send key press event to receiver."
+                );
             },
             crossterm::event::Event::Mouse(mouse_event) => {
-                if let MouseEventKind::Down(button) = mouse_event.kind && button == MouseButton::Left {
-                    tx.send(Event::MousePress((mouse_event.column as i32, mouse_event.row as i32))).expect(
-                        "Should be able to send mouse press event to receiver."
+                let position = (mouse_event.column as i32, mouse_event.row as i32);
+                let event = match mouse_event.kind {
+                    MouseEventKind::Down(button) => Some(Event::MousePress(button, position)),
+                    MouseEventKind::Drag(button) => Some(Event::MouseDrag(button, position)),
+                    MouseEventKind::Up(button) => Some(Event::MouseRelease(button, position)),
+                    _ => None,
+                };
+
+                if let Some(event) = event {
+                    tx.send(event).expect(
+                        "Should be able to send mouse event to receiver."
                     );
                 }
             }
@@ -32,14 +41,83 @@ pub fn loop_key_events(tx: Sender<Event>) -> io::Result<()> {
     }
 }
 
-pub fn handle_key_press(app: &mut App, key: KeyCode) {
+pub fn handle_key_press(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+    // typing a seed takes over the keyboard until it's confirmed or cancelled
+    if let Some(input) = &mut app.seed_input {
+        match key {
+            KeyCode::Char(c) if c.is_ascii_digit() => input.push(c),
+            KeyCode::Backspace => { input.pop(); },
+            KeyCode::Enter => app.confirm_seed(),
+            KeyCode::Esc => app.seed_input = None,
+            _ => {},
+        }
+
+        return;
+    }
+
+    let is_reset = key == KeyCode::Char('r') || key == KeyCode::Char('R');
+    if !is_reset {
+        app.grid.reset_armed = false;
+    }
+
     if key == KeyCode::Char('q') {
         app.exit = true;
+    } else if let Some((dx, dy)) = pan_direction(key, modifiers) {
+        app.grid.pan(dx, dy);
+    } else if key == KeyCode::Char('z') {
+        app.grid.viewport.zoom = app.grid.viewport.zoom.next();
     } else if key == KeyCode::Up {
         app.sidebar.prev();
     } else if key == KeyCode::Down {
         app.sidebar.next();
-    } else if key == KeyCode::Enter {
-        app.sidebar.select(&mut app.grid);
+    } else if key == KeyCode::Enter && matches!(app.grid.state, GridState::Idle) {
+        match app.sidebar.select(&mut app.grid, app.fixed_seed) {
+            Some(AppAction::EditSeed) => app.edit_seed(),
+            Some(AppAction::Save) => app.save_maze(),
+            Some(AppAction::Load) => app.load_maze(),
+            None => {},
+        }
+    } else if key == KeyCode::Char(' ') {
+        app.grid.toggle_pause();
+    } else if is_reset {
+        app.grid.reset();
+    } else if key == KeyCode::Char('+') || key == KeyCode::Char('=') {
+        app.speed.faster();
+    } else if key == KeyCode::Char('-') {
+        app.speed.slower();
+    } else if key == KeyCode::Right {
+        app.step_once();
+    } else if key == KeyCode::Left {
+        app.grid.scrub_back();
+    } else if key == KeyCode::Home {
+        app.grid.scrub_to_start();
+    } else if key == KeyCode::End {
+        app.grid.scrub_to_end();
+    }
+}
+
+// WASD or Shift + arrow keys, the plain arrows are taken by the sidebar and the history
+fn pan_direction(key: KeyCode, modifiers: KeyModifiers) -> Option<(i32, i32)> {
+    let shift = modifiers.contains(KeyModifiers::SHIFT);
+
+    match key {
+        KeyCode::Char('w') => Some((0, -1)),
+        KeyCode::Char('a') => Some((-1, 0)),
+        KeyCode::Char('s') => Some((0, 1)),
+        KeyCode::Char('d') => Some((1, 0)),
+        KeyCode::Up if shift => Some((0, -1)),
+        KeyCode::Left if shift => Some((-1, 0)),
+        KeyCode::Down if shift => Some((0, 1)),
+        KeyCode::Right if shift => Some((1, 0)),
+        _ => None,
+    }
+}
+
+// left button draws walls, middle button mud, right button erases either
+pub fn handle_mouse_paint(app: &mut App, button: MouseButton, position: Coord) {
+    match button {
+        MouseButton::Left => app.grid.paint(position, NodeType::Wall),
+        MouseButton::Right => app.grid.paint(position, NodeType::Empty),
+        MouseButton::Middle => app.grid.paint(position, NodeType::Mud),
     }
 }
\ No newline at end of file
@@ -1,84 +1,308 @@
 use std::{cell::RefCell, rc::Rc};
 
-use ratatui::{style::{Color, Style}, text::Span};
+use mavis::{algorithm::{Algorithm, CellChange, Coord}, grid2d::Grid2D, maze_file::MazeFile, node::{Node, NodeType}};
 
-use crate::algorithm::{Algorithm, Coord};
+use crate::{comparison::Comparison, history::History, utils::{abs_to_grid, line}};
 
 pub enum GridState {
     Idle,
     Generating(Rc<RefCell<dyn Algorithm>>),
+    Paused(Rc<RefCell<dyn Algorithm>>),
     PlacingMarkers(Rc<RefCell<dyn Algorithm>>)
 }
 
-#[derive(PartialEq)]
-pub enum NodeType {
-    Empty,
-    Wall,
-    Visited,
-    Path,
+#[derive(Clone, Copy)]
+pub struct Markers {
+    pub start: Option<Coord>,
+    pub end: Option<Coord>,
 }
 
-impl NodeType {
-    pub fn to_span(&self) -> Span<'_> {
-        match self {
-            Self::Empty => Span::styled(" ", Style::default().fg(Color::White)),
-            Self::Wall => Span::styled("█", Style::default().fg(Color::White)),
-            Self::Visited => Span::styled(".", Style::default().fg(Color::DarkGray)),
-            Self::Path => Span::styled("@", Style::default().fg(Color::LightGreen)),
+impl Markers {
+    pub fn new() -> Self {
+        Self {
+            start: None,
+            end: None,
         }
     }
 }
 
-pub struct Node {
-    pub node_type: NodeType
+#[derive(PartialEq, Clone, Copy)]
+pub enum Zoom {
+    Normal,
+    HalfBlock, // 1 x 2 nodes per character
+    Braille, // 2 x 4 nodes per character
 }
 
-pub struct Markers {
-    pub start: Option<Coord>,
-    pub end: Option<Coord>,
+impl Zoom {
+    // how many nodes a single character on screen covers, (x, y)
+    pub fn scale(self) -> (i32, i32) {
+        match self {
+            Self::Normal => (1, 1),
+            Self::HalfBlock => (1, 2),
+            Self::Braille => (2, 4),
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Self::Normal => Self::HalfBlock,
+            Self::HalfBlock => Self::Braille,
+            Self::Braille => Self::Normal,
+        }
+    }
 }
 
-impl Markers {
+// the part of the grid that fits on screen, the grid itself never changes size with the terminal
+pub struct Viewport {
+    pub offset: Coord, // top left node that's visible
+    pub width: i32, // room on screen in nodes, the grid can be smaller than this
+    pub height: i32,
+    pub zoom: Zoom,
+}
+
+impl Viewport {
     pub fn new() -> Self {
         Self {
-            start: None,
-            end: None,
+            offset: (0, 0),
+            width: 0,
+            height: 0,
+            zoom: Zoom::Normal,
         }
     }
 }
 
 pub struct Grid {
     pub state: GridState,
-    pub content: Vec<Vec<Node>>,
+    pub content: Grid2D,
     pub markers: Markers,
-    pub grid_start: Option<Coord>,
-    pub grid_end: Option<Coord>,
+    pub grid_start: Option<Coord>, // screen position of the viewport's top left node
+    pub viewport: Viewport,
     pub clear: bool,
+    pub reset_armed: bool,
     pub iter_count: i32,
+    pub last_changes: Vec<CellChange>,
+    pub history: History,
+    pub last_paint: Option<Coord>,
+    pub seed: Option<u64>, // what the maze on screen was generated from, gone once it's edited or replaced
+    pub comparison: Option<Rc<RefCell<Comparison>>>, // shown instead of the grid while it's set
 }
 
 impl Grid {
     pub fn new() -> Self {
         Self {
             state: GridState::Idle,
-            content: Vec::new(),
+            content: Grid2D::new(0, 0),
             markers: Markers::new(),
             grid_start: None,
-            grid_end: None,
+            viewport: Viewport::new(),
             clear: false,
+            reset_armed: false,
             iter_count: 0,
+            last_changes: Vec::new(),
+            history: History::new(),
+            last_paint: None,
+            seed: None,
+            comparison: None,
         }
     }
 
-    pub fn height(&self) -> usize {
-        self.content.len()
+    // throws away everything and starts over with an empty grid of the given size
+    pub fn resize(&mut self, width: usize, height: usize) {
+        self.content = Grid2D::new(width, height);
+
+        self.state = GridState::Idle;
+        self.markers = Markers::new();
+        self.iter_count = 0;
+        self.clear = false;
+        self.last_changes.clear();
+        self.history = History::new();
+        self.seed = None;
+        self.comparison = None;
+        self.set_viewport(self.viewport.width, self.viewport.height);
     }
 
-    pub fn width(&self) -> usize {
-        if self.height() == 0 {
-            return 0;
+    // keeps the viewport inside the grid after the terminal or the grid changes size,
+    // the size is in characters on screen
+    pub fn set_viewport(&mut self, width: i32, height: i32) {
+        let (scale_x, scale_y) = self.viewport.zoom.scale();
+
+        self.viewport.width = width * scale_x;
+        self.viewport.height = height * scale_y;
+        self.scroll_by(0, 0);
+    }
+
+    // moves the viewport by a character on screen, which is more than one node when zoomed out
+    pub fn pan(&mut self, dx: i32, dy: i32) {
+        let (scale_x, scale_y) = self.viewport.zoom.scale();
+        self.scroll_by(dx * scale_x, dy * scale_y);
+    }
+
+    // how many nodes are actually on screen
+    pub fn visible_size(&self) -> (i32, i32) {
+        (self.viewport.width.min(self.width() as i32), self.viewport.height.min(self.height() as i32))
+    }
+
+    pub fn scroll_by(&mut self, dx: i32, dy: i32) {
+        let max_x = self.width() as i32 - self.viewport.width;
+        let max_y = self.height() as i32 - self.viewport.height;
+
+        self.viewport.offset = (
+            (self.viewport.offset.0 + dx).clamp(0, max_x.max(0)),
+            (self.viewport.offset.1 + dy).clamp(0, max_y.max(0)),
+        );
+    }
+
+    pub fn in_bounds(&self, coord: Coord) -> bool {
+        self.content.in_bounds(coord)
+    }
+
+    pub fn is_visible(&self, coord: Coord) -> bool {
+        let offset = self.viewport.offset;
+        let (width, height) = self.visible_size();
+
+        offset.0 <= coord.0 && coord.0 < offset.0 + width
+            && offset.1 <= coord.1 && coord.1 < offset.1 + height
+    }
+
+    // node under a screen position, even if it's off the visible part of the grid
+    fn screen_to_grid_unchecked(&self, position: Coord) -> Option<Coord> {
+        let relative = abs_to_grid(position, self.grid_start?);
+        let (scale_x, scale_y) = self.viewport.zoom.scale();
+
+        Some((relative.0 * scale_x + self.viewport.offset.0, relative.1 * scale_y + self.viewport.offset.1))
+    }
+
+    // node under a screen position, None if the position isn't over the visible grid
+    pub fn screen_to_grid(&self, position: Coord) -> Option<Coord> {
+        self.screen_to_grid_unchecked(position).filter(|&coord| self.in_bounds(coord) && self.is_visible(coord))
+    }
+
+    pub fn toggle_pause(&mut self) {
+        // the algorithm only knows about the latest grid, catch up before resuming
+        self.scrub_to_end();
+
+        self.state = match std::mem::replace(&mut self.state, GridState::Idle) {
+            GridState::Generating(algorithm) => GridState::Paused(algorithm),
+            GridState::Paused(algorithm) => GridState::Generating(algorithm),
+            state => state,
+        };
+    }
+
+    // stops whatever is running and clears visited/path nodes, pressing it
+    // again right after also wipes the walls
+    pub fn reset(&mut self) {
+        let wipe_walls = self.reset_armed;
+
+        self.state = GridState::Idle;
+        self.markers = Markers::new();
+        self.iter_count = 0;
+        self.clear = true;
+        self.last_changes.clear();
+        self.history = History::new();
+        self.comparison = None;
+
+        if wipe_walls {
+            self.seed = None;
+            self.content = Grid2D::new(self.width(), self.height());
+        } else {
+            self.content.iter_mut().for_each(Node::clear_overlay);
+        }
+
+        self.reset_armed = !wipe_walls;
+    }
+
+    // the running algorithm's highlighted row, as long as the grid shows where it's at
+    pub fn highlighted_row(&self) -> Option<i32> {
+        match &self.state {
+            GridState::Generating(algorithm) | GridState::Paused(algorithm) if self.history.at_end() => algorithm.borrow().highlighted_row(),
+            _ => None,
+        }
+    }
+
+    pub fn can_scrub(&self) -> bool {
+        // the panes of a comparison don't keep a history
+        !self.history.is_empty() && self.comparison.is_none() && matches!(self.state, GridState::Paused(_) | GridState::Idle)
+    }
+
+    pub fn scrub_back(&mut self) {
+        if self.can_scrub() && self.history.back(&mut self.content) {
+            self.sync_with_history();
+        }
+    }
+
+    pub fn scrub_forward(&mut self) {
+        if self.can_scrub() && self.history.forward(&mut self.content) {
+            self.sync_with_history();
+        }
+    }
+
+    pub fn scrub_to_start(&mut self) {
+        if self.can_scrub() {
+            while self.history.back(&mut self.content) {}
+            self.sync_with_history();
         }
+    }
+
+    pub fn scrub_to_end(&mut self) {
+        if !self.history.at_end() {
+            while self.history.forward(&mut self.content) {}
+            self.sync_with_history();
+        }
+    }
+
+    fn sync_with_history(&mut self) {
+        self.iter_count = self.history.iter_count();
+        self.last_changes = self.history.changes();
+
+        // finished runs drop their markers, everything before that still has them
+        self.markers = if self.history.at_end() && matches!(self.state, GridState::Idle) {
+            Markers::new()
+        } else {
+            self.history.markers
+        };
+    }
+
+    // paints a node under the mouse, filling the gap to the previous one so fast drags don't leave holes
+    pub fn paint(&mut self, position: Coord, node_type: NodeType) {
+        let Some(current) = self.screen_to_grid_unchecked(position) else {
+            return;
+        };
+
+        let mut painted = false;
+        for coord in line(self.last_paint.unwrap_or(current), current) {
+            if self.in_bounds(coord) && self.is_visible(coord) {
+                self.content[coord].set_type(node_type);
+                painted = true;
+            }
+        }
+
+        // whatever was recorded no longer matches the grid
+        if painted {
+            self.seed = None;
+            self.comparison = None;
+            self.history = History::new();
+            self.last_changes.clear();
+        }
+
+        self.last_paint = Some(current);
+    }
 
-        self.content[0].len()
+    // replaces the grid with a loaded maze, taking on its size
+    pub fn load(&mut self, maze: MazeFile) {
+        self.resize(maze.width(), maze.height());
+
+        self.content = maze.content;
+        self.markers = Markers {
+            start: maze.start,
+            end: maze.end,
+        };
     }
-}
\ No newline at end of file
+
+    pub fn height(&self) -> usize {
+        self.content.height()
+    }
+
+    pub fn width(&self) -> usize {
+        self.content.width()
+    }
+}
@@ -0,0 +1,226 @@
+use std::ops::{Index, IndexMut};
+
+use crate::{algorithm::Coord, node::{Node, NodeType}};
+
+const ORTHOGONAL: [Coord; 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+const DIAGONAL: [Coord; 4] = [(1, -1), (1, 1), (-1, 1), (-1, -1)];
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Connectivity {
+    Four, // up, down, left, right
+    Eight, // and the diagonals
+}
+
+// nodes stored row after row in one allocation, indexed with (x, y) coordinates
+#[derive(Clone)]
+pub struct Grid2D {
+    width: usize,
+    height: usize,
+    nodes: Vec<Node>,
+}
+
+impl Grid2D {
+    // a grid of empty nodes
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::filled(width, height, Node::new(NodeType::Empty))
+    }
+
+    // panics when width * height doesn't fit in a usize
+    pub fn filled(width: usize, height: usize, node: Node) -> Self {
+        let size = width.checked_mul(height).unwrap_or_else(|| panic!("a {} x {} grid has too many nodes", width, height));
+
+        Self {
+            width,
+            height,
+            nodes: vec![node; size],
+        }
+    }
+
+    // every row has to be as long as the first one
+    pub fn from_rows(rows: Vec<Vec<Node>>) -> Option<Self> {
+        let width = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Self {
+            width,
+            height: rows.len(),
+            nodes: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn in_bounds(&self, coord: Coord) -> bool {
+        0 <= coord.0 && (coord.0 as usize) < self.width && 0 <= coord.1 && (coord.1 as usize) < self.height
+    }
+
+    fn position(&self, coord: Coord) -> Option<usize> {
+        self.in_bounds(coord).then(|| coord.1 as usize * self.width + coord.0 as usize)
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&Node> {
+        self.position(coord).map(|i| &self.nodes[i])
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut Node> {
+        self.position(coord).map(|i| &mut self.nodes[i])
+    }
+
+    // in bounds neighbors of a node, walls included
+    pub fn neighbors(&self, coord: Coord, connectivity: Connectivity) -> impl Iterator<Item = Coord> + '_ {
+        let diagonals = match connectivity {
+            Connectivity::Four => &DIAGONAL[..0],
+            Connectivity::Eight => &DIAGONAL[..],
+        };
+
+        ORTHOGONAL.iter().chain(diagonals)
+            .map(move |(dx, dy)| (coord.0 + dx, coord.1 + dy))
+            .filter(|&neighbor| self.in_bounds(neighbor))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[Node]> {
+        // chunks() doesn't take a size of 0, which a grid without columns would ask for
+        self.nodes.chunks(self.width.max(1))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Node> {
+        self.nodes.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Node> {
+        self.nodes.iter_mut()
+    }
+
+    // every node along with its coordinates, row by row
+    pub fn enumerate(&self) -> impl Iterator<Item = (Coord, &Node)> {
+        let width = self.width.max(1);
+        self.nodes.iter().enumerate().map(move |(i, node)| (((i % width) as i32, (i / width) as i32), node))
+    }
+}
+
+// for coordinates that are known to be in bounds, panics otherwise
+impl Index<Coord> for Grid2D {
+    type Output = Node;
+
+    fn index(&self, coord: Coord) -> &Node {
+        self.get(coord).unwrap_or_else(|| panic!("{:?} is outside of the {} x {} grid", coord, self.width, self.height))
+    }
+}
+
+impl IndexMut<Coord> for Grid2D {
+    fn index_mut(&mut self, coord: Coord) -> &mut Node {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(coord).unwrap_or_else(|| panic!("{:?} is outside of the {} x {} grid", coord, width, height))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a 3 x 2 grid with every node's weight telling where it is, x + 10 * y
+    fn numbered() -> Grid2D {
+        let rows = (0..2)
+            .map(|y| (0..3).map(|x| Node { weight: x + 10 * y, ..Node::new(NodeType::Empty) }).collect())
+            .collect();
+
+        Grid2D::from_rows(rows).unwrap()
+    }
+
+    fn sorted(mut coords: Vec<Coord>) -> Vec<Coord> {
+        coords.sort();
+        coords
+    }
+
+    #[test]
+    fn gets_nodes_by_coordinates() {
+        let mut grid = numbered();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get((0, 0)).map(|node| node.weight), Some(0));
+        assert_eq!(grid.get((2, 1)).map(|node| node.weight), Some(12));
+        assert_eq!(grid[(1, 1)].weight, 11);
+
+        grid.get_mut((2, 0)).unwrap().weight = 7;
+        assert_eq!(grid[(2, 0)].weight, 7);
+    }
+
+    #[test]
+    fn nothing_outside_of_the_grid() {
+        let mut grid = numbered();
+
+        for coord in [(-1, 0), (0, -1), (3, 0), (0, 2), (3, 2), (i32::MIN, i32::MAX)] {
+            assert!(!grid.in_bounds(coord));
+            assert!(grid.get(coord).is_none());
+            assert!(grid.get_mut(coord).is_none());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "(3, 0) is outside of the 3 x 2 grid")]
+    fn indexing_outside_panics() {
+        let _ = numbered()[(3, 0)];
+    }
+
+    #[test]
+    fn neighbors_stay_in_bounds() {
+        let grid = Grid2D::new(3, 3);
+
+        assert_eq!(sorted(grid.neighbors((0, 0), Connectivity::Four).collect()), [(0, 1), (1, 0)]);
+        assert_eq!(sorted(grid.neighbors((0, 0), Connectivity::Eight).collect()), [(0, 1), (1, 0), (1, 1)]);
+        assert_eq!(sorted(grid.neighbors((2, 1), Connectivity::Four).collect()), [(1, 1), (2, 0), (2, 2)]);
+        assert_eq!(grid.neighbors((1, 1), Connectivity::Four).count(), 4);
+        assert_eq!(grid.neighbors((1, 1), Connectivity::Eight).count(), 8);
+
+        let single = Grid2D::new(1, 1);
+        assert_eq!(single.neighbors((0, 0), Connectivity::Eight).count(), 0);
+    }
+
+    #[test]
+    fn from_rows_rejects_ragged_rows() {
+        let node = Node::new(NodeType::Empty);
+
+        assert!(Grid2D::from_rows(vec![vec![node; 3], vec![node; 2]]).is_none());
+        assert!(Grid2D::from_rows(vec![vec![node; 2], vec![node; 3]]).is_none());
+        assert!(Grid2D::from_rows(Vec::new()).is_some_and(|grid| grid.is_empty()));
+    }
+
+    #[test]
+    fn rows_and_enumerate_go_row_by_row() {
+        let grid = numbered();
+
+        let rows: Vec<Vec<i32>> = grid.rows().map(|row| row.iter().map(|node| node.weight).collect()).collect();
+        assert_eq!(rows, [[0, 1, 2], [10, 11, 12]]);
+
+        for (coord, node) in grid.enumerate() {
+            assert_eq!(node.weight, coord.0 + 10 * coord.1);
+        }
+    }
+
+    #[test]
+    fn empty_grids_have_no_nodes() {
+        for grid in [Grid2D::new(0, 0), Grid2D::new(0, 3), Grid2D::new(3, 0)] {
+            assert!(grid.is_empty());
+            assert!(grid.get((0, 0)).is_none());
+            assert_eq!(grid.rows().count(), 0);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "too many nodes")]
+    fn overflowing_sizes_panic() {
+        Grid2D::new(usize::MAX, 2);
+    }
+}
@@ -0,0 +1,61 @@
+use mavis::{algorithm::{maze, pathfinding}, grid2d::Grid2D, maze_file, runner::{corner_markers, run_to_completion, solve}};
+
+use crate::cli::RunArgs;
+
+// `mavis run`, builds a maze, solves it and prints how that went. returns whether a path was found
+pub fn run(args: RunArgs) -> Result<bool, String> {
+    let (mut grid, start, end) = match &args.load {
+        Some(path) => {
+            let maze = maze_file::load(path).map_err(|e| format!("couldn't load {}: {}", path.display(), e))?;
+            (maze.content, maze.start, maze.end)
+        },
+        None => {
+            let (width, height) = args.size;
+            (Grid2D::new(width, height), None, None)
+        },
+    };
+
+    if grid.is_empty() {
+        return Err(String::from("the maze is empty"));
+    }
+
+    if let Some(name) = &args.maze {
+        let seed = args.seed.unwrap_or_else(rand::random);
+        let mut generator = maze::from_name(name, seed).ok_or_else(|| unknown_maze(name))?;
+        let generated = run_to_completion(generator.as_mut(), &mut grid);
+
+        println!("maze:    {} ({} x {}), seed {}, {} steps in {:?}", name, grid.width(), grid.height(), seed, generated.steps, generated.elapsed);
+    }
+
+    let mut solver = pathfinding::from_name(&args.solver, args.movement, args.heuristic).ok_or_else(|| unknown_solver(&args.solver))?;
+
+    // without markers, go from corner to corner
+    let Some((start, end)) = start.zip(end).or_else(|| corner_markers(&grid)) else {
+        return Err(String::from("the maze has no open nodes"));
+    };
+
+    let solution = solve(&grid, solver.as_mut(), start, end);
+
+    println!("solver:  {} from {:?} to {:?}, {} moves", args.solver, start, end, args.movement.name());
+    match &solution.path {
+        Some(path) => println!("path:    {} moves, cost {:.1}", path.len() - 1, solution.cost),
+        None => println!("path:    none"),
+    }
+    println!("visited: {}", solution.visited);
+    println!("steps:   {}", solution.steps);
+    println!("time:    {:?}", solution.elapsed);
+
+    Ok(solution.path.is_some())
+}
+
+fn unknown_maze(name: &str) -> String {
+    format!(
+        "unknown maze \"{}\", expected backtracker, prims, kruskal, wilson, aldous-broder, hunt-and-kill, division, \
+         eller, sidewinder, binary-tree or noise:<FILL>[:<MUD>]",
+        name,
+    )
+}
+
+fn unknown_solver(name: &str) -> String {
+    format!("unknown solver \"{}\", expected one of {}", name, pathfinding::NAMES.join(", "))
+}
@@ -0,0 +1,142 @@
+use mavis::{algorithm::{CellChange, Coord}, grid2d::Grid2D, node::Node};
+
+use crate::grid::Markers;
+
+// whole nodes, so replaying brings back weights along with types and labels
+struct RecordedChange {
+    coord: Coord,
+    before: Node,
+    after: Node,
+}
+
+struct RecordedStep {
+    iter_count: i32,
+    changes: Vec<RecordedChange>,
+}
+
+// every change a run made to the grid, so it can be replayed backwards and forwards.
+// only the grid is replayed, the algorithm itself stays wherever it was.
+pub struct History {
+    steps: Vec<RecordedStep>,
+    cursor: usize, // how many steps are currently applied to the grid
+    shadow: Grid2D, // nodes as of the latest recorded step
+    pub markers: Markers,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self {
+            steps: Vec::new(),
+            cursor: 0,
+            shadow: Grid2D::new(0, 0),
+            markers: Markers::new(),
+        }
+    }
+
+    pub fn start(content: &Grid2D, markers: Markers) -> Self {
+        Self {
+            steps: Vec::new(),
+            cursor: 0,
+            shadow: content.clone(),
+            markers,
+        }
+    }
+
+    pub fn record(&mut self, iter_count: i32, changes: &[CellChange]) {
+        let changes = changes.iter().map(|c| {
+            let after = Node { node_type: c.node_type, weight: c.weight, label: c.label };
+            let before = std::mem::replace(&mut self.shadow[c.coord], after);
+            RecordedChange { coord: c.coord, before, after }
+        }).collect();
+
+        self.steps.push(RecordedStep { iter_count, changes });
+        self.cursor = self.steps.len();
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.cursor == self.steps.len()
+    }
+
+    // iteration the grid is showing right now
+    pub fn iter_count(&self) -> i32 {
+        if self.cursor == 0 {
+            0
+        } else {
+            self.steps[self.cursor - 1].iter_count
+        }
+    }
+
+    // changes made by the step the grid is showing right now
+    pub fn changes(&self) -> Vec<CellChange> {
+        if self.cursor == 0 {
+            return Vec::new();
+        }
+
+        self.steps[self.cursor - 1].changes.iter()
+            .map(|c| CellChange { coord: c.coord, node_type: c.after.node_type, weight: c.after.weight, label: c.after.label })
+            .collect()
+    }
+
+    pub fn back(&mut self, content: &mut Grid2D) -> bool {
+        if self.cursor == 0 {
+            return false;
+        }
+
+        self.cursor -= 1;
+        for change in self.steps[self.cursor].changes.iter().rev() {
+            content[change.coord] = change.before;
+        }
+
+        true
+    }
+
+    pub fn forward(&mut self, content: &mut Grid2D) -> bool {
+        if self.at_end() {
+            return false;
+        }
+
+        for change in &self.steps[self.cursor].changes {
+            content[change.coord] = change.after;
+        }
+        self.cursor += 1;
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mavis::{algorithm::set_node, node::NodeType};
+
+    use super::*;
+
+    #[test]
+    fn replaying_keeps_weights() {
+        let mut content = Grid2D::filled(3, 1, Node { weight: 9, ..Node::new(NodeType::Mud) });
+        let mut history = History::start(&content, Markers::new());
+
+        let mut changes = Vec::new();
+        set_node(&mut content, &mut changes, (1, 0), NodeType::Visited);
+        set_node(&mut content, &mut changes, (2, 0), NodeType::Empty);
+        history.record(1, &changes);
+
+        assert!(history.back(&mut content));
+        assert!(content.iter().all(|node| node.node_type == NodeType::Mud && node.weight == 9));
+
+        assert!(history.forward(&mut content));
+        assert_eq!((content[(1, 0)].node_type, content[(1, 0)].weight), (NodeType::Visited, 9));
+        assert_eq!((content[(2, 0)].node_type, content[(2, 0)].weight), (NodeType::Empty, 1));
+    }
+}
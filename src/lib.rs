@@ -0,0 +1,10 @@
+//! Maze generators, pathfinders and the grid they work on, without any ui attached.
+//!
+//! Every algorithm implements [`algorithm::Algorithm`] and is driven one step at a time,
+//! [`runner::run_to_completion`] runs one to the end in a single call.
+
+pub mod algorithm;
+pub mod grid2d;
+pub mod maze_file;
+pub mod node;
+pub mod runner;
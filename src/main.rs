@@ -1,34 +1,64 @@
-use std::{io::{self, stdout}, sync::mpsc, thread};
+use std::{env, io::{self, stdout}, process, sync::mpsc, thread};
 
 use crossterm::{event::{DisableMouseCapture, EnableMouseCapture}, execute};
 
-use crate::{app::App, event::{loop_key_events, Event}};
+use crate::{app::App, cli::{Args, Command}, event::{loop_key_events, Event}};
 
 mod app;
+mod bench;
+mod cli;
+mod comparison;
 mod ui;
 mod event;
 mod sidebar;
-mod algorithm;
 mod grid;
+mod headless;
+mod history;
+mod speed;
 mod utils;
 
 fn main() -> io::Result<()> {
+    let command = cli::parse(env::args()).unwrap_or_else(|e| {
+        eprintln!("error: {}\n\n{}", e, cli::USAGE);
+        process::exit(2);
+    });
+
+    match command {
+        Command::Tui(args) => tui(args),
+        Command::Run(args) => match headless::run(args) {
+            Ok(found_path) => process::exit(if found_path { 0 } else { 1 }),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                process::exit(2);
+            },
+        },
+        Command::Bench(args) => bench::run(args).map_err(io::Error::other),
+    }
+}
+
+fn tui(args: Args) -> io::Result<()> {
     let mut terminal = ratatui::init();
     
     // enable mouse detection
     execute!(stdout(), EnableMouseCapture)?;
     
     let mut app = App::new();
+    if let Some(path) = &args.save {
+        app.save_path = path.clone();
+    }
+    if let Some((width, height)) = args.size {
+        app.grid.resize(width, height);
+    }
+    app.fixed_seed = args.seed;
 
     // Event-loop threads
     let (event_tx, event_rx) = mpsc::channel::<Event>();
 
-    let key_event_tx = event_tx.clone();
     thread::spawn(|| {
-        loop_key_events(key_event_tx).expect("Failed to loop events.");
+        loop_key_events(event_tx).expect("Failed to loop events.");
     });
 
-    let app_result = app.run(&mut terminal, event_rx, event_tx);
+    let app_result = app.run(&mut terminal, event_rx, args.load);
     
     ratatui::restore();
     execute!(stdout(), DisableMouseCapture)?;
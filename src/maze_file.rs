@@ -0,0 +1,203 @@
+//! Plain-text maze format.
+//!
+//! ```text
+//! mavis 1
+//! 7 3
+//! S..#...
+//! .#.#.5.
+//! ...#..E
+//! ```
+//!
+//! The first line is the format header, the second one is `<width> <height>`,
+//! followed by exactly `height` rows of `width` characters:
+//!
+//! - `#` wall
+//! - `.` empty node
+//! - `S` / `E` start and end marker (both optional, on an empty node)
+//! - `2`..`9` weighted node, drawn as mud (`1` is the same as `.`)
+
+use std::{fs, io, path::Path};
+
+use crate::{algorithm::Coord, grid2d::Grid2D, node::{Node, NodeType}};
+
+const HEADER: &str = "mavis 1";
+
+pub struct MazeFile {
+    pub content: Grid2D,
+    pub start: Option<Coord>,
+    pub end: Option<Coord>,
+}
+
+impl MazeFile {
+    pub fn width(&self) -> usize {
+        self.content.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.content.height()
+    }
+}
+
+pub fn save(path: &Path, content: &Grid2D, start: Option<Coord>, end: Option<Coord>) -> io::Result<()> {
+    let mut text = format!("{}\n{} {}\n", HEADER, content.width(), content.height());
+
+    for (y, row) in content.rows().enumerate() {
+        for (x, node) in row.iter().enumerate() {
+            let coord = (x as i32, y as i32);
+
+            // visited/path nodes are saved as whatever terrain is underneath
+            let c = if Some(coord) == start {
+                'S'
+            } else if Some(coord) == end {
+                'E'
+            } else if node.node_type == NodeType::Wall {
+                '#'
+            } else if node.weight > 1 {
+                char::from_digit(node.weight.min(9) as u32, 10).unwrap_or('9')
+            } else {
+                '.'
+            };
+
+            text.push(c);
+        }
+
+        text.push('\n');
+    }
+
+    fs::write(path, text)
+}
+
+pub fn load(path: &Path) -> io::Result<MazeFile> {
+    parse(&fs::read_to_string(path)?)
+}
+
+pub fn parse(text: &str) -> io::Result<MazeFile> {
+    let mut lines = text.lines();
+
+    if lines.next().map(str::trim) != Some(HEADER) {
+        return Err(invalid(format!("expected a \"{}\" header", HEADER)));
+    }
+
+    let size: Vec<usize> = lines.next()
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(|n| n.parse().map_err(|_| invalid(format!("invalid size \"{}\"", n))))
+        .collect::<io::Result<_>>()?;
+
+    let [width, height] = size[..] else {
+        return Err(invalid("expected \"<width> <height>\" on the second line".to_string()));
+    };
+
+    if width == 0 || height == 0 {
+        return Err(invalid(format!("a {} x {} maze has no nodes", width, height)));
+    }
+
+    // the rows are read before the grid is built, so a size the file doesn't back up is never allocated
+    let mut rows = Vec::new();
+    let (mut start, mut end) = (None, None);
+
+    for y in 0..height {
+        let line = lines.next().ok_or_else(|| invalid(format!("expected {} rows, found {}", height, y)))?;
+        if line.chars().count() != width {
+            return Err(invalid(format!("row {} should be {} characters long", y + 1, width)));
+        }
+
+        let mut row = Vec::with_capacity(width);
+        for (x, c) in line.chars().enumerate() {
+            let coord = (x as i32, y as i32);
+
+            row.push(match c {
+                '#' => Node::new(NodeType::Wall),
+                '.' | '1' => Node::new(NodeType::Empty),
+                'S' => {
+                    start = Some(coord);
+                    Node::new(NodeType::Empty)
+                },
+                'E' => {
+                    end = Some(coord);
+                    Node::new(NodeType::Empty)
+                },
+                '2'..='9' => Node {
+                    weight: c.to_digit(10).unwrap_or(1) as i32,
+                    ..Node::new(NodeType::Mud)
+                },
+                _ => return Err(invalid(format!("unknown character '{}' at ({}, {})", c, x, y))),
+            });
+        }
+
+        rows.push(row);
+    }
+
+    let content = Grid2D::from_rows(rows).ok_or_else(|| invalid("rows of different lengths".to_string()))?;
+
+    Ok(MazeFile { content, start, end })
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(text: &str) -> String {
+        match parse(text) {
+            Ok(_) => panic!("{:?} should not parse", text),
+            Err(e) => {
+                assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+                e.to_string()
+            },
+        }
+    }
+
+    #[test]
+    fn rejects_a_bad_header() {
+        assert!(error("").contains("header"));
+        assert!(error("mavis 2\n1 1\n.\n").contains("header"));
+    }
+
+    #[test]
+    fn rejects_a_bad_size() {
+        assert!(error("mavis 1\n").contains("<width> <height>"));
+        assert!(error("mavis 1\n3\n...\n").contains("<width> <height>"));
+        assert!(error("mavis 1\n3 x\n...\n").contains("invalid size"));
+        assert!(error("mavis 1\n-3 1\n...\n").contains("invalid size"));
+        assert!(error("mavis 1\n0 3\n").contains("no nodes"));
+        assert!(error("mavis 1\n3 0\n").contains("no nodes"));
+        assert!(error("mavis 1\n100000 100000\n...\n").contains("row 1"));
+        assert!(error("mavis 1\n3 18446744073709551615\n...\n").contains("expected 18446744073709551615 rows"));
+    }
+
+    #[test]
+    fn rejects_short_rows() {
+        assert!(error("mavis 1\n3 2\n...\n").contains("expected 2 rows"));
+        assert!(error("mavis 1\n3 2\n...\n..\n").contains("row 2"));
+        assert!(error("mavis 1\n3 2\n...\n....\n").contains("row 2"));
+    }
+
+    #[test]
+    fn rejects_unknown_characters() {
+        assert!(error("mavis 1\n3 1\n.x.\n").contains("'x' at (1, 0)"));
+        assert!(error("mavis 1\n3 1\n.0.\n").contains("'0'"));
+    }
+
+    #[test]
+    fn round_trips_markers_and_weights() {
+        let text = "mavis 1\n7 3\nS..#...\n.#.#.5.\n...#..E\n";
+        let maze = parse(text).unwrap();
+
+        assert_eq!((maze.width(), maze.height()), (7, 3));
+        assert_eq!((maze.start, maze.end), (Some((0, 0)), Some((6, 2))));
+        assert_eq!(maze.content[(3, 0)].node_type, NodeType::Wall);
+        assert_eq!(maze.content[(5, 1)].node_type, NodeType::Mud);
+        assert_eq!(maze.content[(5, 1)].weight, 5);
+
+        let path = std::env::temp_dir().join(format!("mavis-maze-file-{}.txt", std::process::id()));
+        save(&path, &maze.content, maze.start, maze.end).unwrap();
+        let saved = fs::read_to_string(&path);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(saved.unwrap(), text);
+    }
+}
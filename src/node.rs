@@ -0,0 +1,50 @@
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum NodeType {
+    Empty,
+    Wall,
+    Visited,
+    Path,
+    Frontier,
+    Mud,
+    Walk, // a random walk, drawn apart from the maze it's carving
+}
+
+// cost of stepping onto a mud node, plain nodes cost 1
+pub const MUD_WEIGHT: i32 = 5;
+
+#[derive(Clone, Copy)]
+pub struct Node {
+    pub node_type: NodeType,
+    pub weight: i32,
+    pub label: Option<u32>, // e.g. the set a node belongs to while a maze is generated, drawn as a color
+}
+
+impl Node {
+    pub fn new(node_type: NodeType) -> Self {
+        let weight = if node_type == NodeType::Mud { MUD_WEIGHT } else { 1 };
+
+        Self {
+            node_type,
+            weight,
+            label: None,
+        }
+    }
+
+    // terrain types come with their own weight, anything drawn on top keeps the one underneath
+    pub fn set_type(&mut self, node_type: NodeType) {
+        if matches!(node_type, NodeType::Empty | NodeType::Wall | NodeType::Mud) {
+            *self = Node::new(node_type);
+        } else {
+            self.node_type = node_type;
+        }
+    }
+
+    // turns visited/path/etc. nodes back into the terrain underneath
+    pub fn clear_overlay(&mut self) {
+        self.label = None;
+
+        if !matches!(self.node_type, NodeType::Empty | NodeType::Wall | NodeType::Mud) {
+            self.node_type = if self.weight > 1 { NodeType::Mud } else { NodeType::Empty };
+        }
+    }
+}
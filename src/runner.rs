@@ -0,0 +1,70 @@
+use std::time::{Duration, Instant};
+
+use crate::{algorithm::{pathfinding::movement::path_cost, Algorithm, AlgorithmResult, Coord}, grid2d::Grid2D, node::{Node, NodeType}};
+
+pub struct Run {
+    pub result: AlgorithmResult,
+    pub steps: u64,
+    pub elapsed: Duration,
+}
+
+// steps an algorithm until it's done, the same loop the app runs but without waiting in between
+pub fn run_to_completion(algorithm: &mut dyn Algorithm, grid: &mut Grid2D) -> Run {
+    let started = Instant::now();
+    let mut steps = 0;
+
+    loop {
+        let result = algorithm.step(grid);
+        steps += 1;
+
+        if !matches!(result, AlgorithmResult::ModifiedGrid(_)) {
+            return Run { result, steps, elapsed: started.elapsed() };
+        }
+    }
+}
+
+// open node closest to the given corner
+pub fn nearest_open(grid: &Grid2D, corner: Coord) -> Option<Coord> {
+    grid.enumerate()
+        .filter(|(_, node)| node.node_type != NodeType::Wall)
+        .map(|(coord, _)| coord)
+        .min_by_key(|coord| coord.0.abs_diff(corner.0) + coord.1.abs_diff(corner.1))
+}
+
+// start and end for mazes without markers, from the top left corner to the bottom right one
+pub fn corner_markers(grid: &Grid2D) -> Option<(Coord, Coord)> {
+    let far_corner = (grid.width() as i32 - 1, grid.height() as i32 - 1);
+    Some((nearest_open(grid, (0, 0))?, nearest_open(grid, far_corner)?))
+}
+
+// what a pathfinder did on a maze, measured the same way for every solver
+pub struct Solution {
+    pub path: Option<Vec<Coord>>,
+    pub cost: f64, // weights along the path, diagonal moves count √2 times theirs
+    pub visited: usize,
+    pub steps: u64,
+    pub elapsed: Duration,
+}
+
+// runs a solver on a copy of the grid, so the same maze can be handed to the next one
+pub fn solve(grid: &Grid2D, solver: &mut dyn Algorithm, start: Coord, end: Coord) -> Solution {
+    let mut grid = grid.clone();
+    grid.iter_mut().for_each(Node::clear_overlay);
+
+    solver.init(start, end);
+    let run = run_to_completion(solver, &mut grid);
+
+    let path = match run.result {
+        AlgorithmResult::Done(path) => path,
+        _ => None,
+    };
+    let cost = path.as_deref().map_or(0.0, |path| path_cost(&grid, path));
+
+    Solution {
+        path,
+        cost,
+        visited: grid.iter().filter(|node| node.node_type == NodeType::Visited).count(),
+        steps: run.steps,
+        elapsed: run.elapsed,
+    }
+}
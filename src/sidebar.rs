@@ -2,11 +2,14 @@ use std::{cell::RefCell, rc::Rc};
 
 use ratatui::widgets::ListState;
 
-use crate::{algorithm::{maze::noise_map::NoiseMap, pathfinding::a_star::AStar, Algorithm}, grid::{Grid, GridState}};
+use mavis::algorithm::{maze::{aldous_broder::AldousBroder, binary_tree::BinaryTree, eller::Eller, hunt_and_kill::HuntAndKill, kruskal::Kruskal, noise_map::NoiseMap, prims::Prims, recursive_backtracker::RecursiveBacktracker, recursive_division::RecursiveDivision, sidewinder::Sidewinder, wilson::Wilson}, pathfinding::{a_star::AStar, bfs::Bfs, dijkstra::Dijkstra, movement::Movement}, Algorithm};
+
+use crate::{comparison::Comparison, grid::{Grid, GridState}};
 
 pub struct Sidebar {
     pub page: SidebarPage,
     pub state: ListState,
+    pub movement: Movement, // how every pathfinder started from here moves
 }
 
 impl Sidebar {
@@ -17,12 +20,17 @@ impl Sidebar {
         Self {
             page: SidebarPage::Main,
             state,
+            movement: Movement::FourWay,
         }
     }
 
+    pub fn options(&self) -> Vec<SidebarOption> {
+        self.page.options(self.movement)
+    }
+
     pub fn next(&mut self) {
         if let Some(o) = self.state.selected() {
-            if o == self.page.options().len() - 1 {
+            if o == self.options().len() - 1 {
                 self.state.select(Some(0));
             } else {
                 self.state.select(Some(o+1));
@@ -35,7 +43,7 @@ impl Sidebar {
     pub fn prev(&mut self) {
         if let Some(o) = self.state.selected() {
             if o == 0 {
-                self.state.select(Some(self.page.options().len() - 1));
+                self.state.select(Some(self.options().len() - 1));
             } else {
                 self.state.select(Some(o-1));
             }
@@ -44,29 +52,63 @@ impl Sidebar {
         }
     }
 
-    pub fn select(&mut self, grid: &mut Grid) {
-        if let Some(o) = self.state.selected() && let Some(action) = &self.page.options()[o].action {
+    // actions that need more than the grid are handed back to the caller. mazes are generated
+    // from `fixed_seed`, or a random one if there's none
+    pub fn select(&mut self, grid: &mut Grid, fixed_seed: Option<u64>) -> Option<AppAction> {
+        if let Some(o) = self.state.selected() && let Some(action) = &self.options()[o].action {
             match action {
                 SidebarAction::SwitchPage(page) => {
                     self.page = page.clone();
                     self.state.select(Some(0));
                 },
-                SidebarAction::InitAlgorithm(algorithm) => {
+                SidebarAction::InitAlgorithm(new_algorithm) => {
                     self.page = SidebarPage::Main;
                     self.state.select(Some(0));
 
-                    grid.state = GridState::Generating(Rc::clone(algorithm));
-                },
-                SidebarAction::InitPlaceMarkers(algorithm) => {
-                    self.page = SidebarPage::Main;
-                    self.state.select(Some(0));
+                    grid.scrub_to_end();
+                    grid.comparison = None;
 
-                    grid.state = GridState::PlacingMarkers(Rc::clone(algorithm));
+                    let seed = fixed_seed.unwrap_or_else(rand::random);
+                    grid.seed = Some(seed);
+                    grid.state = GridState::Generating(new_algorithm(seed));
+                },
+                SidebarAction::InitPlaceMarkers(new_algorithm) => {
+                    grid.comparison = None;
+                    self.start_pathfinding(grid, new_algorithm(self.movement));
+                },
+                SidebarAction::InitComparison(new_comparison) => {
+                    let comparison = Rc::new(RefCell::new(new_comparison(self.movement)));
+                    grid.comparison = Some(Rc::clone(&comparison));
+                    self.start_pathfinding(grid, comparison);
                 },
+                SidebarAction::CycleMovement => self.movement = self.movement.next(),
+                SidebarAction::FitGridToWindow => {
+                    grid.resize(grid.viewport.width as usize, grid.viewport.height as usize);
+                },
+                SidebarAction::App(app_action) => return Some(*app_action),
             }
         } else {
             self.state.select(Some(0));
         }
+
+        None
+    }
+
+    fn start_pathfinding(&mut self, grid: &mut Grid, algorithm: Rc<RefCell<dyn Algorithm>>) {
+        self.page = SidebarPage::Main;
+        self.state.select(Some(0));
+
+        grid.scrub_to_end();
+
+        // a loaded maze can come with its markers already placed
+        if let (Some(start), Some(end)) = (grid.markers.start, grid.markers.end) {
+            algorithm.borrow_mut().init(start, end);
+
+            grid.iter_count = 0;
+            grid.state = GridState::Generating(algorithm);
+        } else {
+            grid.state = GridState::PlacingMarkers(algorithm);
+        }
     }
 }
 
@@ -75,28 +117,58 @@ pub enum SidebarPage {
     Main,
     MazeGenerationAlgorithms,
     PathfindingAlgorithms,
+    Comparisons,
 }
 
 impl SidebarPage {
-    pub fn options(&self) -> Vec<SidebarOption> {
+    pub fn options(&self, movement: Movement) -> Vec<SidebarOption> {
         match self {
             SidebarPage::Main =>
                 vec![
                     SidebarOption::new("View Maze Algorithms", Some(SidebarAction::SwitchPage(SidebarPage::MazeGenerationAlgorithms))),
                     SidebarOption::new("View Pathfinding Algorithms", Some(SidebarAction::SwitchPage(SidebarPage::PathfindingAlgorithms))),
+                    SidebarOption::new("Compare Pathfinders", Some(SidebarAction::SwitchPage(SidebarPage::Comparisons))),
+                    SidebarOption::new(
+                        match movement {
+                            Movement::FourWay => "Movement: 4-way",
+                            Movement::EightWay => "Movement: 8-way",
+                            Movement::EightWayNoCornerCutting => "Movement: 8-way, No Corners",
+                        },
+                        Some(SidebarAction::CycleMovement),
+                    ),
+                    SidebarOption::new("Fit Grid to Window", Some(SidebarAction::FitGridToWindow)),
+                    SidebarOption::new("Set Seed", Some(SidebarAction::App(AppAction::EditSeed))),
+                    SidebarOption::new("Save Maze", Some(SidebarAction::App(AppAction::Save))),
+                    SidebarOption::new("Load Maze", Some(SidebarAction::App(AppAction::Load))),
                 ],
             SidebarPage::MazeGenerationAlgorithms =>
                 vec![
-                    SidebarOption::new("Recursive Backtracking", None),
-                    SidebarOption::new("Prim's", None),
-                    SidebarOption::new("Noise Map", Some(SidebarAction::InitAlgorithm(Rc::new(RefCell::new(NoiseMap::new(10)))))),
+                    SidebarOption::new("Recursive Backtracking", Some(SidebarAction::InitAlgorithm(|seed| Rc::new(RefCell::new(RecursiveBacktracker::new(seed)))))),
+                    SidebarOption::new("Prim's", Some(SidebarAction::InitAlgorithm(|seed| Rc::new(RefCell::new(Prims::new(seed)))))),
+                    SidebarOption::new("Kruskal's", Some(SidebarAction::InitAlgorithm(|seed| Rc::new(RefCell::new(Kruskal::new(seed)))))),
+                    SidebarOption::new("Wilson's", Some(SidebarAction::InitAlgorithm(|seed| Rc::new(RefCell::new(Wilson::new(seed)))))),
+                    SidebarOption::new("Aldous-Broder", Some(SidebarAction::InitAlgorithm(|seed| Rc::new(RefCell::new(AldousBroder::new(seed)))))),
+                    SidebarOption::new("Hunt-and-Kill", Some(SidebarAction::InitAlgorithm(|seed| Rc::new(RefCell::new(HuntAndKill::new(seed)))))),
+                    SidebarOption::new("Recursive Division", Some(SidebarAction::InitAlgorithm(|seed| Rc::new(RefCell::new(RecursiveDivision::new(seed)))))),
+                    SidebarOption::new("Eller's", Some(SidebarAction::InitAlgorithm(|seed| Rc::new(RefCell::new(Eller::new(seed)))))),
+                    SidebarOption::new("Sidewinder", Some(SidebarAction::InitAlgorithm(|seed| Rc::new(RefCell::new(Sidewinder::new(seed)))))),
+                    SidebarOption::new("Binary Tree", Some(SidebarAction::InitAlgorithm(|seed| Rc::new(RefCell::new(BinaryTree::new(seed)))))),
+                    SidebarOption::new("Noise Map", Some(SidebarAction::InitAlgorithm(|seed| Rc::new(RefCell::new(NoiseMap::new(10, 0, seed)))))),
+                    SidebarOption::new("Noise Map (with Mud)", Some(SidebarAction::InitAlgorithm(|seed| Rc::new(RefCell::new(NoiseMap::new(10, 25, seed)))))),
                     SidebarOption::new("Back", Some(SidebarAction::SwitchPage(SidebarPage::Main)))
                 ],
             SidebarPage::PathfindingAlgorithms =>
                 vec![
-                    SidebarOption::new("A*", Some(SidebarAction::InitPlaceMarkers(Rc::new(RefCell::new(AStar::new()))))), // Some(SidebarAction::InitAlgorithm(Rc::new(RefCell::new(AStar::new((0, 0), (106, 37))))))
-                    SidebarOption::new("BFS", None),
-                    SidebarOption::new("Dijkstra's", None),
+                    SidebarOption::new("A*", Some(SidebarAction::InitPlaceMarkers(|movement| Rc::new(RefCell::new(AStar::new(movement, movement.heuristic())))))),
+                    SidebarOption::new("BFS", Some(SidebarAction::InitPlaceMarkers(|movement| Rc::new(RefCell::new(Bfs::new(movement)))))),
+                    SidebarOption::new("Dijkstra's", Some(SidebarAction::InitPlaceMarkers(|movement| Rc::new(RefCell::new(Dijkstra::new(movement)))))),
+                    SidebarOption::new("Back", Some(SidebarAction::SwitchPage(SidebarPage::Main)))
+                ],
+            SidebarPage::Comparisons =>
+                vec![
+                    SidebarOption::new("A* vs BFS", Some(SidebarAction::InitComparison(|movement| Comparison::new(vec![("A*", Box::new(AStar::new(movement, movement.heuristic()))), ("BFS", Box::new(Bfs::new(movement)))])))),
+                    SidebarOption::new("A* vs Dijkstra's", Some(SidebarAction::InitComparison(|movement| Comparison::new(vec![("A*", Box::new(AStar::new(movement, movement.heuristic()))), ("Dijkstra's", Box::new(Dijkstra::new(movement)))])))),
+                    SidebarOption::new("BFS vs Dijkstra's", Some(SidebarAction::InitComparison(|movement| Comparison::new(vec![("BFS", Box::new(Bfs::new(movement))), ("Dijkstra's", Box::new(Dijkstra::new(movement)))])))),
                     SidebarOption::new("Back", Some(SidebarAction::SwitchPage(SidebarPage::Main)))
                 ]
         }
@@ -119,6 +191,17 @@ impl SidebarOption {
 
 enum SidebarAction {
     SwitchPage(SidebarPage),
-    InitAlgorithm(Rc<RefCell<dyn Algorithm>>),
-    InitPlaceMarkers(Rc<RefCell<dyn Algorithm>>),
+    InitAlgorithm(fn(u64) -> Rc<RefCell<dyn Algorithm>>), // built once the seed is known
+    InitPlaceMarkers(fn(Movement) -> Rc<RefCell<dyn Algorithm>>), // built with the movement picked at the time
+    InitComparison(fn(Movement) -> Comparison),
+    CycleMovement,
+    FitGridToWindow,
+    App(AppAction),
+}
+
+#[derive(Clone, Copy)]
+pub enum AppAction {
+    EditSeed,
+    Save,
+    Load,
 }
@@ -0,0 +1,58 @@
+use std::time::{Duration, Instant};
+
+// steps per second the +/- keys cycle through
+const LEVELS: [u32; 14] = [1, 2, 5, 10, 20, 50, 100, 200, 500, 1_000, 2_000, 5_000, 10_000, 20_000];
+const DEFAULT_LEVEL: usize = 9;
+
+// roughly 60 redraws per second, no matter how fast the algorithm steps
+pub const FRAME_TIME: Duration = Duration::from_millis(16);
+
+pub struct Speed {
+    level: usize,
+    last_tick: Instant,
+    budget: f64,
+}
+
+impl Speed {
+    pub fn new() -> Self {
+        Self {
+            level: DEFAULT_LEVEL,
+            last_tick: Instant::now(),
+            budget: 0.0,
+        }
+    }
+
+    pub fn steps_per_second(&self) -> u32 {
+        LEVELS[self.level]
+    }
+
+    pub fn faster(&mut self) {
+        self.level = (self.level + 1).min(LEVELS.len() - 1);
+    }
+
+    pub fn slower(&mut self) {
+        self.level = self.level.saturating_sub(1);
+    }
+
+    // number of steps that should have happened since the last call
+    pub fn steps_due(&mut self) -> u32 {
+        let now = Instant::now();
+        let steps_per_second = self.steps_per_second() as f64;
+
+        self.budget += (now - self.last_tick).as_secs_f64() * steps_per_second;
+        self.last_tick = now;
+
+        // never catch up on more than a frame's worth, otherwise time spent
+        // paused or placing markers turns into one huge burst of steps
+        self.budget = self.budget.min((steps_per_second * FRAME_TIME.as_secs_f64()).max(1.0));
+
+        let steps = self.budget.floor();
+        self.budget -= steps;
+
+        steps as u32
+    }
+
+    pub fn until_next_step(&self) -> Duration {
+        Duration::from_secs_f64((1.0 - self.budget).max(0.0) / self.steps_per_second() as f64)
+    }
+}
@@ -7,7 +7,11 @@ use ratatui::{
     Frame,
 };
 
-use crate::{app::App, grid::{GridState, Node, NodeType}};
+use std::{collections::HashSet, rc::Rc};
+
+use mavis::{algorithm::{CellChange, Coord}, grid2d::Grid2D, node::{Node, NodeType}};
+
+use crate::{app::App, comparison::{Comparison, Summary}, grid::{Grid, GridState, Zoom}, history::History};
 
 pub fn draw(app: &mut App, frame: &mut Frame) {
     let app_layout = Layout::vertical([
@@ -17,29 +21,82 @@ pub fn draw(app: &mut App, frame: &mut Frame) {
     ]);
     let [header_area, _, main_area] = app_layout.areas(frame.area());
 
-    draw_header(frame, header_area, app.grid.iter_count);
+    draw_header(app, frame, header_area);
     draw_main_area(app, frame, main_area);
 }
 
-fn draw_header(frame: &mut Frame, header_area: Rect, iter_count: i32) {
+fn draw_header(app: &App, frame: &mut Frame, header_area: Rect) {
     let header_area_layout = Layout::horizontal([
         Constraint::Percentage(50),
         Constraint::Percentage(50),
     ]);
     let [header_left, header_right] = header_area_layout.areas(header_area);
 
-    let iteration_text = format!("Iterations: {}", iter_count);
-    let iteration_text_count = iteration_text.len();
+    let mut iteration_text = format!("Speed: {} steps/s | Iterations: {}", app.speed.steps_per_second(), app.grid.iter_count);
+    if let Some(seed) = app.grid.seed {
+        iteration_text += &format!(" | Seed: {}", seed);
+    }
+    if let GridState::Paused(_) = app.grid.state {
+        iteration_text += " (paused)";
+    }
 
     frame.render_widget(Paragraph::new(Text::from(mavis_title())), header_left);
-    frame.render_widget(Paragraph::new(iteration_text), Rect {
-        x: header_right.right() - (iteration_text_count as u16) - 1,
-        y: header_right.bottom() - 1,
-        width: iteration_text_count as u16,
+
+    draw_header_line(frame, header_right, 0, &iteration_text, Style::default());
+    if !app.grid.last_changes.is_empty() {
+        draw_header_line(frame, header_right, 1, &describe_changes(&app.grid.last_changes), Style::default());
+    }
+    if let Some(input) = &app.seed_input {
+        let prompt = format!("Seed: {}_ ([Enter] set, empty for random, [Esc] cancel)", input);
+        draw_header_line(frame, header_right, 2, &prompt, Style::default().fg(Color::Yellow));
+    } else if let Some(status) = &app.status {
+        draw_header_line(frame, header_right, 2, status, Style::default().fg(Color::Yellow));
+    }
+}
+
+// right aligned line of text, rows are counted up from the bottom of the area
+fn draw_header_line(frame: &mut Frame, area: Rect, row: u16, text: &str, style: Style) {
+    if area.height <= row || area.width < 2 {
+        return;
+    }
+
+    let text_count = (text.chars().count() as u16).min(area.width - 1);
+    frame.render_widget(Paragraph::new(text).style(style), Rect {
+        x: area.right() - text_count - 1,
+        y: area.bottom() - 1 - row,
+        width: text_count,
         height: 1,
     });
 }
 
+// e.g. " History [=====|-----] 120/240 "
+fn history_scrubber(history: &History, bar_width: usize) -> String {
+    let filled = (history.cursor() * bar_width) / history.len().max(1);
+    let bar: String = (0..bar_width).map(|i| match i.cmp(&filled) {
+        std::cmp::Ordering::Less => '=',
+        std::cmp::Ordering::Equal => '|',
+        std::cmp::Ordering::Greater => '-',
+    }).collect();
+
+    format!(" History [{}] {}/{} ", bar, history.cursor(), history.len())
+}
+
+// e.g. "Last step: (3, 4) -> Visited, (3, 5) -> Visited (+2 more)"
+fn describe_changes(changes: &[CellChange]) -> String {
+    const SHOWN: usize = 3;
+
+    let described: Vec<String> = changes.iter()
+        .take(SHOWN)
+        .map(|c| format!("({}, {}) -> {:?}", c.coord.0, c.coord.1, c.node_type))
+        .collect();
+
+    if changes.len() > SHOWN {
+        format!("Last step: {} (+{} more)", described.join(", "), changes.len() - SHOWN)
+    } else {
+        format!("Last step: {}", described.join(", "))
+    }
+}
+
 fn draw_main_area(app: &mut App, frame: &mut Frame, main_area: Rect) {
     let main_area_layout = Layout::horizontal([
         Constraint::Percentage(70),
@@ -53,29 +110,47 @@ fn draw_main_area(app: &mut App, frame: &mut Frame, main_area: Rect) {
 }
 
 fn draw_grid(app: &mut App, frame: &mut Frame, grid: Rect) {
-    let (map_width, map_height) = (grid.width - 2, grid.height - 2);
+    let (map_width, map_height) = (grid.width.saturating_sub(2), grid.height.saturating_sub(2));
 
-    // generate new grid on resize
-    if app.grid.width() != map_width as usize || app.grid.height() != map_height as usize {
-        app.grid.content = (0..map_height).map(|_| {
-            (0..map_width).map(|_| Node { node_type: NodeType::Empty }).collect()
-        }).collect();
+    // the grid takes the size of the first window it's drawn in (unless one was picked already),
+    // after that resizing the terminal only changes how much of it is visible
+    if app.grid.height() == 0 && map_width > 0 && map_height > 0 {
+        app.grid.resize(map_width as usize, map_height as usize);
+    }
 
-        app.grid.grid_start = Some((grid.left() as i32 + 1, grid.top() as i32 + 1));
-        app.grid.grid_end = Some((grid.right() as i32 - 2, grid.bottom() as i32 - 2));
+    // panes take over once the markers are placed
+    if let Some(comparison) = &app.grid.comparison && !matches!(app.grid.state, GridState::PlacingMarkers(_)) {
+        let comparison = Rc::clone(comparison);
+        draw_comparison(&mut app.grid, &comparison.borrow(), frame, grid);
+        return;
     }
 
+    app.grid.grid_start = Some((grid.left() as i32 + 1, grid.top() as i32 + 1));
+    app.grid.set_viewport(map_width as i32, map_height as i32);
+    let viewport = &app.grid.viewport;
+    let (visible_width, visible_height) = app.grid.visible_size();
+
     let border_title = if let GridState::PlacingMarkers(_) = app.grid.state {
-        if app.grid.markers.start == None {
+        if app.grid.markers.start.is_none() {
             String::from(" Click anywhere on the grid to place the START marker... ")
         } else {
             String::from(" Click anywhere on the grid to place the END marker... ")
         }
+    } else if visible_width < app.grid.width() as i32 || visible_height < app.grid.height() as i32 {
+        format!(
+            " Main Grid ({} x {}, showing {}..{} x {}..{}) ",
+            app.grid.width(), app.grid.height(),
+            viewport.offset.0, viewport.offset.0 + visible_width,
+            viewport.offset.1, viewport.offset.1 + visible_height,
+        )
     } else {
-        format!(" Main Grid ({} x {})", map_width, map_height)
+        format!(" Main Grid ({} x {}) ", app.grid.width(), app.grid.height())
     };
 
-    let border = Block::bordered().title(border_title).border_set(border::THICK);
+    let mut border = Block::bordered().title(border_title).border_set(border::THICK);
+    if app.grid.can_scrub() {
+        border = border.title_bottom(history_scrubber(&app.grid.history, map_width as usize / 2));
+    }
 
     frame.render_widget(border, Rect {
         x: grid.left(),
@@ -84,45 +159,292 @@ fn draw_grid(app: &mut App, frame: &mut Frame, grid: Rect) {
         height: grid.height,
     });
 
-    // draw nodes on screen
-    let content: Vec<Line> = app.grid.content.iter().map(|grid_row| {
-        let nodes: Vec<Span> = grid_row.iter().map(|n| n.node_type.to_span()).collect();
-        Line::from(nodes)
-    }).collect();
-
-    frame.render_widget(Paragraph::new(Text::from(content)), Rect {
+    let nodes_area = Rect {
         x: grid.left() + 1,
         y: grid.top() + 1,
         width: map_width,
         height: map_height,
-    });
+    };
+    draw_nodes(frame, &app.grid, &app.grid.content, &app.grid.last_changes, nodes_area);
+
+    if let Some(row) = app.grid.highlighted_row() {
+        highlight_row(frame, &app.grid, row, nodes_area);
+    }
+}
+
+// a background across the visible part of a grid row, whichever zoom it's drawn at
+fn highlight_row(frame: &mut Frame, grid: &Grid, row: i32, area: Rect) {
+    let (_, scale_y) = grid.viewport.zoom.scale();
+    let (_, visible_height) = grid.visible_size();
+    let (columns, _) = visible_characters(grid);
 
-    if let Some(position) = app.grid.markers.start {
-        frame.render_widget(Text::from("S"), Rect {
-            x: position.0 as u16,
-            y: position.1 as u16,
-            width: 1,
-            height: 1
-        });
+    let offset = row - grid.viewport.offset.1;
+    if offset < 0 || offset >= visible_height {
+        return;
     }
 
-    if let Some(position) = app.grid.markers.end {
-        frame.render_widget(Text::from("E"), Rect {
-            x: position.0 as u16,
-            y: position.1 as u16,
-            width: 1,
-            height: 1
-        });
+    let highlight = Rect {
+        x: area.left(),
+        y: area.top() + (offset / scale_y) as u16,
+        width: columns as u16,
+        height: 1,
+    }.intersection(area);
+    frame.buffer_mut().set_style(highlight, Style::default().bg(Color::DarkGray));
+}
+
+// the panes side by side, each showing its own copy of the maze through the same viewport
+fn draw_comparison(grid: &mut Grid, comparison: &Comparison, frame: &mut Frame, area: Rect) {
+    let panes = comparison.panes.len() as u32;
+    let areas = Layout::horizontal((0..panes).map(|_| Constraint::Ratio(1, panes))).split(area);
+
+    let inner = Block::bordered().inner(areas[0]);
+    grid.grid_start = Some((inner.left() as i32, inner.top() as i32));
+    grid.set_viewport(inner.width as i32, inner.height as i32);
+
+    for (pane, &pane_area) in comparison.panes.iter().zip(areas.iter()) {
+        let mut border = Block::bordered()
+            .title(format!(" {} | Iterations: {} ", pane.name, pane.iter_count))
+            .border_set(border::THICK);
+        if let Some(summary) = &pane.summary {
+            border = border.title_bottom(describe_summary(summary));
+        }
+
+        let inner = border.inner(pane_area);
+        frame.render_widget(border, pane_area);
+        draw_nodes(frame, grid, &pane.content, &pane.last_changes, inner);
     }
 }
 
+// e.g. " 42 moves, cost 50.0 | 300 visited "
+fn describe_summary(summary: &Summary) -> String {
+    match summary.path {
+        Some((moves, cost)) => format!(" {} moves, cost {:.1} | {} visited ", moves, cost, summary.visited),
+        None => format!(" No path | {} visited ", summary.visited),
+    }
+}
+
+// the visible part of `content` at the grid's zoom, with the markers on top
+fn draw_nodes(frame: &mut Frame, grid: &Grid, content: &Grid2D, last_changes: &[CellChange], area: Rect) {
+    let viewport = &grid.viewport;
+    let lines = match viewport.zoom {
+        Zoom::Normal => normal_lines(grid, content, last_changes),
+        Zoom::HalfBlock => half_block_lines(grid, content),
+        Zoom::Braille => braille_lines(grid, content),
+    };
+
+    frame.render_widget(Paragraph::new(Text::from(lines)), area);
+
+    let (scale_x, scale_y) = viewport.zoom.scale();
+    for (marker, label) in [(grid.markers.start, "S"), (grid.markers.end, "E")] {
+        if let Some(coord) = marker && grid.is_visible(coord) {
+            frame.render_widget(Text::from(label), Rect {
+                x: area.left() + ((coord.0 - viewport.offset.0) / scale_x) as u16,
+                y: area.top() + ((coord.1 - viewport.offset.1) / scale_y) as u16,
+                width: 1,
+                height: 1
+            });
+        }
+    }
+}
+
+fn node_span(node: &Node) -> Span<'static> {
+    if let (NodeType::Empty, Some(label)) = (node.node_type, node.label) {
+        return Span::styled("█", Style::default().fg(label_color(label)));
+    }
+
+    match node.node_type {
+        NodeType::Empty => Span::styled(" ", Style::default().fg(Color::White)),
+        NodeType::Wall => Span::styled("█", Style::default().fg(Color::White)),
+        NodeType::Visited => Span::styled(".", Style::default().fg(Color::DarkGray)),
+        NodeType::Path => Span::styled("@", Style::default().fg(Color::LightGreen)),
+        NodeType::Frontier => Span::styled("▒", Style::default().fg(Color::Yellow)),
+        NodeType::Mud => Span::styled("░", Style::default().fg(Color::Rgb(150, 100, 50))),
+        NodeType::Walk => Span::styled("▓", Style::default().fg(Color::LightMagenta)),
+    }
+}
+
+// used when zoomed out and there's no room for a character per node
+fn node_color(node_type: NodeType) -> Color {
+    match node_type {
+        NodeType::Empty => Color::Reset,
+        NodeType::Wall => Color::White,
+        NodeType::Visited => Color::DarkGray,
+        NodeType::Path => Color::LightGreen,
+        NodeType::Frontier => Color::Yellow,
+        NodeType::Mud => Color::Rgb(150, 100, 50),
+        NodeType::Walk => Color::LightMagenta,
+    }
+}
+
+// same, with labelled nodes in their label's color
+fn labelled_node_color(node: &Node) -> Color {
+    match (node.node_type, node.label) {
+        (NodeType::Empty, Some(label)) => label_color(label),
+        _ => node_color(node.node_type),
+    }
+}
+
+// labels are spread over the palette, so sets that are numbered next to each other don't look alike
+fn label_color(label: u32) -> Color {
+    const PALETTE: [Color; 12] = [
+        Color::Red, Color::Green, Color::Yellow, Color::Blue, Color::Magenta, Color::Cyan,
+        Color::LightRed, Color::LightGreen, Color::LightYellow, Color::LightBlue, Color::LightMagenta, Color::LightCyan,
+    ];
+
+    PALETTE[(label.wrapping_mul(2_654_435_761) >> 16) as usize % PALETTE.len()]
+}
+
+// which type gets to pick the color when several share a character
+fn node_priority(node_type: NodeType) -> u8 {
+    match node_type {
+        NodeType::Empty => 0,
+        NodeType::Wall => 1,
+        NodeType::Mud => 2,
+        NodeType::Visited => 3,
+        NodeType::Frontier => 4,
+        NodeType::Walk => 5,
+        NodeType::Path => 6,
+    }
+}
+
+// a character per node, highlighting whatever the last step touched
+fn normal_lines(grid: &Grid, content: &Grid2D, last_changes: &[CellChange]) -> Vec<Line<'static>> {
+    let (offset_x, offset_y) = (grid.viewport.offset.0 as usize, grid.viewport.offset.1 as usize);
+    let (visible_width, visible_height) = grid.visible_size();
+    let touched: HashSet<Coord> = last_changes.iter().map(|c| c.coord).collect();
+
+    content.rows().enumerate().skip(offset_y).take(visible_height as usize).map(|(y, grid_row)| {
+        let nodes: Vec<Span> = grid_row.iter().enumerate().skip(offset_x).take(visible_width as usize).map(|(x, n)| {
+            if touched.contains(&(x as i32, y as i32)) {
+                node_span(n).patch_style(Style::default().bg(Color::Blue))
+            } else {
+                node_span(n)
+            }
+        }).collect();
+        Line::from(nodes)
+    }).collect()
+}
+
+// "▀" with the top node as the foreground and the bottom one as the background
+fn half_block_lines(grid: &Grid, content: &Grid2D) -> Vec<Line<'static>> {
+    let (columns, rows) = visible_characters(grid);
+    let node_color = |x: i32, y: i32| content.get((x, y)).map_or(Color::Reset, labelled_node_color);
+
+    (0..rows).map(|row| {
+        let y = grid.viewport.offset.1 + row * 2;
+        let characters: Vec<Span> = (0..columns).map(|column| {
+            let x = grid.viewport.offset.0 + column;
+            Span::styled("▀", Style::default().fg(node_color(x, y)).bg(node_color(x, y + 1)))
+        }).collect();
+        Line::from(characters)
+    }).collect()
+}
+
+fn braille_lines(grid: &Grid, content: &Grid2D) -> Vec<Line<'static>> {
+    let (columns, rows) = visible_characters(grid);
+
+    (0..rows).map(|row| {
+        let characters: Vec<Span> = (0..columns).map(|column| {
+            let origin = (grid.viewport.offset.0 + column * 2, grid.viewport.offset.1 + row * 4);
+            braille_span(|dx, dy| node_type_at(content, (origin.0 + dx, origin.1 + dy)))
+        }).collect();
+        Line::from(characters)
+    }).collect()
+}
+
+// a braille character for a 2 x 4 block, every non-empty node is a dot
+// and the most interesting node type in the block picks the color
+fn braille_span(sample: impl Fn(i32, i32) -> Option<NodeType>) -> Span<'static> {
+    // bit of each dot, indexed by [x][y]
+    const DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+    let mut bits = 0;
+    let mut shown = NodeType::Empty;
+    for (dx, column) in DOTS.iter().enumerate() {
+        for (dy, bit) in column.iter().enumerate() {
+            let Some(node_type) = sample(dx as i32, dy as i32) else {
+                continue;
+            };
+
+            if node_type != NodeType::Empty {
+                bits |= bit;
+            }
+            if node_priority(node_type) > node_priority(shown) {
+                shown = node_type;
+            }
+        }
+    }
+
+    let character = char::from_u32(0x2800 + bits).unwrap_or(' ');
+    Span::styled(character.to_string(), Style::default().fg(node_color(shown)))
+}
+
+// how many characters the visible part of the grid takes up at the current zoom
+fn visible_characters(grid: &Grid) -> (i32, i32) {
+    let (visible_width, visible_height) = grid.visible_size();
+    let (scale_x, scale_y) = grid.viewport.zoom.scale();
+
+    ((visible_width + scale_x - 1) / scale_x, (visible_height + scale_y - 1) / scale_y)
+}
+
+fn node_type_at(content: &Grid2D, coord: Coord) -> Option<NodeType> {
+    content.get(coord).map(|node| node.node_type)
+}
+
+// the whole grid squeezed into the area, with the part that's on screen highlighted
+fn draw_minimap(app: &App, frame: &mut Frame, minimap_area: Rect) {
+    let block = Block::bordered().title(" Minimap ");
+    let inner = block.inner(minimap_area);
+    frame.render_widget(block, minimap_area);
+
+    let grid = &app.grid;
+    if inner.width == 0 || inner.height == 0 || grid.height() == 0 {
+        return;
+    }
+
+    // nodes per braille dot, a dot samples the node at its top left
+    let dots = (inner.width as f64 * 2.0, inner.height as f64 * 4.0);
+    let scale = (grid.width() as f64 / dots.0, grid.height() as f64 / dots.1);
+    let (visible_width, visible_height) = grid.visible_size();
+    let viewport = &grid.viewport;
+
+    let content: Vec<Line> = (0..inner.height).map(|row| {
+        let characters: Vec<Span> = (0..inner.width).map(|column| {
+            let dot_origin = (column as f64 * 2.0, row as f64 * 4.0);
+            let span = braille_span(|dx, dy| {
+                let x = ((dot_origin.0 + dx as f64) * scale.0) as i32;
+                let y = ((dot_origin.1 + dy as f64) * scale.1) as i32;
+                node_type_at(&grid.content, (x, y))
+            });
+
+            // nodes this character covers
+            let from = ((dot_origin.0 * scale.0) as i32, (dot_origin.1 * scale.1) as i32);
+            let to = (((dot_origin.0 + 2.0) * scale.0).ceil() as i32, ((dot_origin.1 + 4.0) * scale.1).ceil() as i32);
+
+            let in_viewport = from.0 < viewport.offset.0 + visible_width && viewport.offset.0 < to.0
+                && from.1 < viewport.offset.1 + visible_height && viewport.offset.1 < to.1;
+
+            if in_viewport {
+                span.patch_style(Style::default().bg(Color::DarkGray))
+            } else {
+                span
+            }
+        }).collect();
+        Line::from(characters)
+    }).collect();
+
+    frame.render_widget(Paragraph::new(Text::from(content)), inner);
+}
+
 fn draw_sidebar(app: &mut App, frame: &mut Frame, sidebar_area: Rect) {
     let sidebar_area_container = Layout::vertical([
-        Constraint::Percentage(10),
-        Constraint::Percentage(70),
-        Constraint::Percentage(20),
+        Constraint::Percentage(40),
+        Constraint::Percentage(30),
+        Constraint::Percentage(30),
     ]);
-    let [_, sidebar, sidebar_description] = sidebar_area_container.areas(sidebar_area);
+    let [sidebar, minimap, sidebar_description] = sidebar_area_container.areas(sidebar_area);
+
+    draw_minimap(app, frame, minimap);
 
     let sidebar_description_text = Paragraph::new(
         Text::from(
@@ -131,6 +453,11 @@ fn draw_sidebar(app: &mut App, frame: &mut Frame, sidebar_area: Rect) {
                 Line::from(
                     Span::styled("[R] Reset/Stop Algorithm", Style::default().fg(Color::White))
                 ),
+                Line::from(Span::styled("[+/-] Faster/Slower", Style::default().fg(Color::White))),
+                Line::from(Span::styled("[Right] Step (while paused)", Style::default().fg(Color::White))),
+                Line::from(Span::styled("[Left/Right/Home/End] Rewind", Style::default().fg(Color::White))),
+                Line::from(Span::styled("[L/M/R Mouse] Wall/Mud/Erase", Style::default().fg(Color::White))),
+                Line::from(Span::styled("[WASD/Shift+Arrows] Pan, [Z] Zoom", Style::default().fg(Color::White))),
                 Line::from(Span::styled("[Q] Quit Application", Style::default().fg(Color::White)))
             ]
         )
@@ -138,7 +465,7 @@ fn draw_sidebar(app: &mut App, frame: &mut Frame, sidebar_area: Rect) {
     frame.render_widget(sidebar_description_text, sidebar_description);
 
     let options = List::new(
-        app.sidebar.page
+        app.sidebar
             .options()
             .iter()
             .map(|o| o.title)
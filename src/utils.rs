@@ -1,5 +1,30 @@
-use crate::algorithm::Coord;
+use mavis::algorithm::Coord;
 
 pub fn abs_to_grid(abs_position: Coord, grid_start: Coord) -> Coord {
     (abs_position.0 - grid_start.0, abs_position.1 - grid_start.1)
+}
+
+// every coordinate on the straight line between two points, both ends included
+pub fn line(from: Coord, to: Coord) -> Vec<Coord> {
+    let (dx, dy) = ((to.0 - from.0).abs(), -(to.1 - from.1).abs());
+    let (sx, sy) = ((to.0 - from.0).signum(), (to.1 - from.1).signum());
+    let mut error = dx + dy;
+    let mut current = from;
+    let mut points = vec![current];
+
+    while current != to {
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            current.0 += sx;
+        }
+        if doubled <= dx {
+            error += dx;
+            current.1 += sy;
+        }
+
+        points.push(current);
+    }
+
+    points
 }
\ No newline at end of file